@@ -11,18 +11,17 @@ fn main() -> Result<(), anyhow::Error> {
         "src/abi/profitandloss_contract.rs",
     ];
 
-    let mut i = 0;
-    for f in file_names {
+    // sanitize fields and attributes starting with an underscore
+    let regex = Regex::new(r#"("\w+"\s?:\s?")_(\w+")"#).unwrap();
+    // sanitize fields and attributes with multiple consecutive underscores
+    let re = Regex::new(r"_+").unwrap();
+
+    for (i, f) in file_names.iter().enumerate() {
         let contents = fs::read_to_string(f)
             .expect("Should have been able to read the file");
 
-        // sanitize fields and attributes starting with an underscore
-        let regex = Regex::new(r#"("\w+"\s?:\s?")_(\w+")"#).unwrap();
         let sanitized_abi_file = regex.replace_all(contents.as_str(), "${1}u_${2}");
 
-        // sanitize fields and attributes with multiple consecutive underscores
-        let re = Regex::new(r"_+").unwrap();
-
         let re_sanitized_abi_file = re.replace_all(&sanitized_abi_file, |caps: &regex::Captures| {
                 let count = caps[0].len();
                 let replacement = format!("{}_", "_u".repeat(count - 1));
@@ -32,8 +31,6 @@ fn main() -> Result<(), anyhow::Error> {
         Abigen::from_bytes("Contract", re_sanitized_abi_file.as_bytes())?
             .generate()?
             .write_to_file(file_output_names[i])?;
-
-        i = i+1;
     }
 
     Ok(())
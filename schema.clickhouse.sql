@@ -0,0 +1,114 @@
+-- ClickHouse schema for the db_out module (substreams-sink-sql with engine: clickhouse)
+-- ReplacingMergeTree keeps the latest version of upserted rows, query with FINAL for exact results
+
+CREATE TABLE IF NOT EXISTS markets (
+    condition_id      String,
+    question_id       String,
+    total_volume      Decimal(38, 6),
+    total_trades      UInt64,
+    total_fees        Decimal(38, 6),
+    winning_outcome   String,
+    resolution_price  Decimal(38, 6),
+    is_neg_risk       Bool,
+    created_at        Nullable(DateTime),
+    resolved_at       Nullable(DateTime),
+    block_number      UInt64
+) ENGINE = ReplacingMergeTree(block_number)
+ORDER BY (condition_id);
+
+CREATE TABLE IF NOT EXISTS trades (
+    evt_tx_hash       String,
+    evt_index         UInt32,
+    evt_block_number  UInt64,
+    evt_block_time    DateTime,
+    venue             LowCardinality(String),
+    exchange_address  String,
+    order_hash        String,
+    maker_address     String,
+    taker_address     String,
+    maker_side        LowCardinality(String),
+    taker_side        LowCardinality(String),
+    token_id          String,
+    outcome_index     UInt32,
+    condition_id      String,
+    question_id       String,
+    price             Decimal(38, 6),
+    shares            Decimal(38, 6),
+    usdc_amount       Decimal(38, 6),
+    fee_usdc          Decimal(38, 6),
+    is_taker_order    Bool
+) ENGINE = ReplacingMergeTree(evt_block_number)
+ORDER BY (evt_tx_hash, evt_index);
+
+CREATE TABLE IF NOT EXISTS token_prices (
+    token_id          String,
+    price             Decimal(38, 6),
+    block_number      UInt64
+) ENGINE = ReplacingMergeTree(block_number)
+ORDER BY (token_id);
+
+CREATE TABLE IF NOT EXISTS transfers (
+    evt_tx_hash       String,
+    evt_index         UInt32,
+    batch_index       UInt32,
+    evt_block_number  UInt64,
+    evt_block_time    DateTime,
+    operator          String,
+    from_address      String,
+    to_address        String,
+    token_id          String,
+    amount            Decimal(38, 6)
+) ENGINE = ReplacingMergeTree(evt_block_number)
+ORDER BY (evt_tx_hash, evt_index, batch_index);
+
+CREATE TABLE IF NOT EXISTS reward_claims (
+    evt_tx_hash       String,
+    evt_index         UInt32,
+    evt_block_number  UInt64,
+    evt_block_time    DateTime,
+    airdrop_recipient String,
+    asset             LowCardinality(String),
+    amount            Decimal(38, 18)
+) ENGINE = ReplacingMergeTree(evt_block_number)
+ORDER BY (evt_tx_hash, evt_index);
+
+CREATE TABLE IF NOT EXISTS collateral_tokens (
+    address           String,
+    symbol            String,
+    decimals          UInt32,
+    source            LowCardinality(String),
+    first_seen_block  UInt64
+) ENGINE = ReplacingMergeTree(first_seen_block)
+ORDER BY (address);
+
+CREATE TABLE IF NOT EXISTS positions (
+    wallet            String,
+    token_id          String,
+    balance           Decimal(38, 6),
+    block_number      UInt64,
+    updated_at        DateTime
+) ENGINE = ReplacingMergeTree(block_number)
+ORDER BY (wallet, token_id);
+
+CREATE TABLE IF NOT EXISTS wallet_pnl (
+    wallet            String,
+    net_usdc          Decimal(38, 6),
+    block_number      UInt64,
+    updated_at        DateTime
+) ENGINE = ReplacingMergeTree(block_number)
+ORDER BY (wallet);
+
+-- Wallet P&L like the Dune query: net USDC plus open positions at the latest traded price
+CREATE VIEW IF NOT EXISTS wallet_pnl_view AS
+SELECT
+    w.wallet,
+    w.net_usdc,
+    ifNull(s.share_value, 0) AS share_value,
+    w.net_usdc + ifNull(s.share_value, 0) AS total_pnl
+FROM wallet_pnl AS w FINAL
+LEFT JOIN (
+    SELECT p.wallet, sum(p.balance * tp.price) AS share_value
+    FROM positions AS p FINAL
+    INNER JOIN token_prices AS tp FINAL ON tp.token_id = p.token_id
+    GROUP BY p.wallet
+) AS s ON s.wallet = w.wallet;
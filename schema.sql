@@ -0,0 +1,117 @@
+-- Postgres schema for the db_out module (substreams-sink-sql)
+-- Amounts are USDC / share units already divided by 10^6 (UMA rewards by 10^18)
+
+CREATE TABLE IF NOT EXISTS markets (
+    condition_id      TEXT NOT NULL PRIMARY KEY,
+    question_id       TEXT,
+    total_volume      NUMERIC,
+    total_trades      NUMERIC,
+    total_fees        NUMERIC,
+    winning_outcome   TEXT,
+    resolution_price  NUMERIC,
+    is_neg_risk       BOOLEAN,
+    created_at        TIMESTAMP,
+    resolved_at       TIMESTAMP,
+    block_number      BIGINT
+);
+
+CREATE TABLE IF NOT EXISTS trades (
+    evt_tx_hash       TEXT NOT NULL,
+    evt_index         INTEGER NOT NULL,
+    evt_block_number  BIGINT,
+    evt_block_time    TIMESTAMP,
+    venue             TEXT,
+    exchange_address  TEXT,
+    order_hash        TEXT,
+    maker_address     TEXT,
+    taker_address     TEXT,
+    maker_side        TEXT,
+    taker_side        TEXT,
+    token_id          TEXT,
+    outcome_index     INTEGER,
+    condition_id      TEXT,
+    question_id       TEXT,
+    price             NUMERIC,
+    shares            NUMERIC,
+    usdc_amount       NUMERIC,
+    fee_usdc          NUMERIC,
+    is_taker_order    BOOLEAN,
+    PRIMARY KEY (evt_tx_hash, evt_index)
+);
+
+CREATE INDEX IF NOT EXISTS trades_maker_idx ON trades (maker_address);
+CREATE INDEX IF NOT EXISTS trades_taker_idx ON trades (taker_address);
+CREATE INDEX IF NOT EXISTS trades_condition_idx ON trades (condition_id);
+
+CREATE TABLE IF NOT EXISTS token_prices (
+    token_id          TEXT NOT NULL PRIMARY KEY,
+    price             NUMERIC,
+    block_number      BIGINT
+);
+
+CREATE TABLE IF NOT EXISTS transfers (
+    evt_tx_hash       TEXT NOT NULL,
+    evt_index         INTEGER NOT NULL,
+    batch_index       INTEGER NOT NULL,
+    evt_block_number  BIGINT,
+    evt_block_time    TIMESTAMP,
+    operator          TEXT,
+    from_address      TEXT,
+    to_address        TEXT,
+    token_id          TEXT,
+    amount            NUMERIC,
+    PRIMARY KEY (evt_tx_hash, evt_index, batch_index)
+);
+
+CREATE INDEX IF NOT EXISTS transfers_from_idx ON transfers (from_address);
+CREATE INDEX IF NOT EXISTS transfers_to_idx ON transfers (to_address);
+
+CREATE TABLE IF NOT EXISTS reward_claims (
+    evt_tx_hash       TEXT NOT NULL,
+    evt_index         INTEGER NOT NULL,
+    evt_block_number  BIGINT,
+    evt_block_time    TIMESTAMP,
+    airdrop_recipient TEXT,
+    asset             TEXT,
+    amount            NUMERIC,
+    PRIMARY KEY (evt_tx_hash, evt_index)
+);
+
+CREATE TABLE IF NOT EXISTS collateral_tokens (
+    address           TEXT NOT NULL PRIMARY KEY,
+    symbol            TEXT,
+    decimals          INTEGER,
+    source            TEXT,
+    first_seen_block  BIGINT
+);
+
+CREATE TABLE IF NOT EXISTS positions (
+    wallet            TEXT NOT NULL,
+    token_id          TEXT NOT NULL,
+    balance           NUMERIC,
+    block_number      BIGINT,
+    updated_at        TIMESTAMP,
+    PRIMARY KEY (wallet, token_id)
+);
+
+CREATE TABLE IF NOT EXISTS wallet_pnl (
+    wallet            TEXT NOT NULL PRIMARY KEY,
+    net_usdc          NUMERIC,
+    block_number      BIGINT,
+    updated_at        TIMESTAMP
+);
+
+-- Wallet P&L like the Dune query: net USDC plus open positions at the latest traded price
+CREATE OR REPLACE VIEW wallet_pnl_view AS
+SELECT
+    w.wallet,
+    w.net_usdc,
+    COALESCE(s.share_value, 0) AS share_value,
+    w.net_usdc + COALESCE(s.share_value, 0) AS total_pnl
+FROM wallet_pnl w
+LEFT JOIN (
+    SELECT p.wallet, SUM(p.balance * tp.price) AS share_value
+    FROM positions p
+    JOIN token_prices tp ON tp.token_id = p.token_id
+    GROUP BY p.wallet
+) s ON s.wallet = w.wallet;
@@ -20,6 +20,8 @@ echo ""
 
 # Get the latest block data and extract user info
 substreams run substreams.yaml map_pure_dune_pnl \
+    -p index_wallet_activity="wallets=$TARGET_ADDRESS" \
+    -p map_pure_dune_pnl="wallets=$TARGET_ADDRESS" \
     --start-block $START_BLOCK \
     --stop-block $END_BLOCK \
     --substreams-endpoint polygon.streamingfast.io:443 2>/dev/null | \
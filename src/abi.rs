@@ -1,18 +1,182 @@
+#[allow(clippy::all)]
+#[rustfmt::skip]
+pub mod profitandloss_contract;
+
+use crate::network::NetworkProfile;
 use crate::pb::contract::v1 as contract;
+use hex_literal::hex;
+use num_traits::ToPrimitive;
+use profitandloss_contract::events as ctf;
+use substreams::scalar::BigInt;
 use substreams::Hex;
 use substreams_ethereum::pb::eth::v2::Log;
+use substreams_ethereum::Event;
+
+// Event signatures (topic0)
+pub const TOKEN_REGISTERED_SIG: [u8; 32] = hex!("bc9a2432e8aeb48327246cddd6e872ef452812b4243c04e6bfb786a2cd8faf0d");
+pub const ORDER_FILLED_SIG: [u8; 32] = hex!("d0a08e8c493f9c94f29311604c9de1b4e8c8d4c06bd0c789af57f2d65bfec0f6");
+pub const ORDERS_MATCHED_SIG: [u8; 32] = hex!("63bf4d16b7fa898ef4c4b2b6d90fd201e9c56313b65638af6088d149d2ce956c");
+pub const ORDER_CANCELLED_SIG: [u8; 32] = hex!("5152abf959f6564662358c2e52b702259b78bac5ee7842a0f01937e670efcc7d");
+pub const FEE_CHARGED_SIG: [u8; 32] = hex!("acffcc86834d0f1a64b0d5a675798deed6ff0bcfc2231edd3480e7288dba7ff4");
+pub const TRADING_PAUSED_SIG: [u8; 32] = hex!("203c4bd3e526634f661575359ff30de3b0edaba6c2cb1eac60f730b6d2d9d536");
+pub const TRADING_UNPAUSED_SIG: [u8; 32] = hex!("a1e8a54850dbd7f520bcc09f47bff152294b77b2081da545a7adf531b7ea283b");
+pub const NEW_ADMIN_SIG: [u8; 32] = hex!("f9ffabca9c8276e99321725bcb43fb076a6c66a54b7f21c4e8146d8519b417dc");
+pub const NEW_OPERATOR_SIG: [u8; 32] = hex!("f1e04d73c4304b5ff164f9d10c7473e2a1593b740674a6107975e2a7001c1e5c");
+pub const REMOVED_ADMIN_SIG: [u8; 32] = hex!("787a2e12f4a55b658b8f573c32432ee11a5e8b51677d1e1e937aaf6a0bb5776e");
+pub const REMOVED_OPERATOR_SIG: [u8; 32] = hex!("f7262ed0443cc211121ceb1a80d69004f319245615a7488f951f1437fd91642c");
+pub const PROXY_FACTORY_UPDATED_SIG: [u8; 32] = hex!("3053c6252a932554235c173caffc1913604dba3a41cee89516f631c4a1a50a37");
+pub const SAFE_FACTORY_UPDATED_SIG: [u8; 32] = hex!("9726d7faf7429d6b059560dc858ed769377ccdf8b7541eabe12b22548719831f");
+pub const FPMM_CREATION_SIG: [u8; 32] = hex!("92e0912d3d7f3192cad5c7ae3b47fb97f9c465c1dd12a5c24fd901ddb3905f43");
+pub const FPMM_BUY_SIG: [u8; 32] = hex!("4f62630f51608fc8a7603a9391a5101e58bd7c276139366fc107dc3b67c3dcf8");
+pub const FPMM_SELL_SIG: [u8; 32] = hex!("adcf2a240ed9300d681d9a3f5382b6c1beed1b7e46643e0c7b42cbe6e2d766b4");
+pub const ERC20_TRANSFER_SIG: [u8; 32] = hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+pub const ERC20_APPROVAL_SIG: [u8; 32] = hex!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925");
+pub const TRANSFER_SINGLE_SIG: [u8; 32] = hex!("c3d58168c5ae7397731d063d5bbf3d657854427343f4c083240f7aacaa2d0f62");
+pub const TRANSFER_BATCH_SIG: [u8; 32] = hex!("4a39dc06d4c0dbc64b70af90fd698a233a518aa5d07e595d983b8c0526c8f7fb");
+pub const QUESTION_INITIALIZED_SIG: [u8; 32] = hex!("2aac65a22b07e92208fb8fb75a7e3eba7a57064d03f620a427ce3e3c222762d0");
+pub const CLAIMED_SIG: [u8; 32] = hex!("d8138f8a3f377c5259ca548e70e4c2de94f129f5a11036a15b69513cba2b426a");
+// CTF events, decoded through the generated bindings
+pub const CONDITION_PREPARATION_SIG: [u8; 32] = hex!("ab3760c3bd2bb38b5bcf54dc79802ed67338b4cf29f3054ded67ed24661e4177");
+pub const CONDITION_RESOLUTION_SIG: [u8; 32] = hex!("b44d84d3289691f71497564b85d4233648d9dbae8cbdbb4329f301c3a0185894");
+pub const POSITION_SPLIT_SIG: [u8; 32] = hex!("2e6bb91f8cbcda0c93623c54d0403a43514fabc40084ec96b6d5379a74786298");
+pub const POSITIONS_MERGE_SIG: [u8; 32] = hex!("6f13ca62553fcc2bcd2372180a43949c1e4cebba603901ede2f4e14f36b282ca");
+pub const PAYOUT_REDEMPTION_SIG: [u8; 32] = hex!("2682012a4a4f1973119f1c9b90745d1bd91fa2bab387344f044cb3586864d18d");
 
-// Helper function to decode uint256 from bytes
-fn u256_from_bytes(bytes: &[u8]) -> u64 {
+// Helper function to decode uint256 from bytes (full 256-bit precision, token IDs do not fit in u64)
+fn u256_from_bytes(bytes: &[u8]) -> BigInt {
     if bytes.len() >= 32 {
-        // Take the last 8 bytes for u64 (assuming values fit in u64)
-        let mut result = 0u64;
-        for i in 24..32 {
-            result = (result << 8) | bytes[i] as u64;
-        }
-        result
+        BigInt::from_unsigned_bytes_be(&bytes[0..32])
+    } else {
+        BigInt::zero()
+    }
+}
+
+// Helper function to read a uint256 used as an ABI length or offset
+fn u256_to_usize(bytes: &[u8]) -> usize {
+    if bytes.len() >= 32 && bytes[0..24].iter().all(|b| *b == 0) {
+        bytes[24..32].iter().fold(0usize, |acc, b| (acc << 8) | *b as usize)
     } else {
-        0
+        usize::MAX
+    }
+}
+
+// Outcome slot counts are small, out of range values saturate instead of panicking like BigInt::to_u64
+fn slot_count(count: BigInt) -> u32 {
+    let count: num_bigint::BigInt = count.into();
+    count.to_u32().unwrap_or(u32::MAX)
+}
+
+// Every event the package reads, decoded once per log by route()
+pub enum DecodedEvent {
+    TokenRegistered(contract::TokenRegistered),
+    OrderFilled(contract::OrderFilled),
+    OrdersMatched(contract::OrdersMatched),
+    Exchange(ExchangeEventType),
+    FpmmCreation(contract::FpmmFactoryCreation),
+    FpmmTrade(FpmmTradeData),
+    Ctf(CtfEventType),
+    Erc1155TransferSingle(contract::Erc1155TransferSingle),
+    Erc1155TransferBatch(contract::Erc1155TransferBatch),
+    Erc20Transfer(contract::Erc20Transfer),
+    UsdcApproval(contract::UsdcApproval),
+    MerkleClaimed(contract::MerkleDistributorClaimed),
+    QuestionInitialized(QuestionInitializedData),
+}
+
+// Single dispatch on (address, topic0): the network's protocol contracts first, then the events
+// matched on topic0 at any address. ERC1155 and ERC20 transfers come back as the generic records,
+// including the CTF's and the collateral's own
+pub fn route(network: &NetworkProfile, log: &Log) -> Option<DecodedEvent> {
+    let topic0: [u8; 32] = log.topics.first()?.as_slice().try_into().ok()?;
+
+    if network.is_exchange(&log.address) {
+        return match topic0 {
+            TOKEN_REGISTERED_SIG => decode_token_registered(log).map(DecodedEvent::TokenRegistered),
+            ORDER_FILLED_SIG => decode_order_filled(log).map(DecodedEvent::OrderFilled),
+            ORDERS_MATCHED_SIG => decode_orders_matched(log).map(DecodedEvent::OrdersMatched),
+            _ => decode_exchange_events(log).map(DecodedEvent::Exchange),
+        };
+    }
+    if log.address == network.fpmm_factory {
+        return decode_fpmm_creation(log).map(DecodedEvent::FpmmCreation);
+    }
+    if network.is_merkle_distributor(&log.address) && topic0 == CLAIMED_SIG {
+        return decode_merkle_claimed(log).map(DecodedEvent::MerkleClaimed);
+    }
+    if log.address == network.usdc && topic0 == ERC20_APPROVAL_SIG {
+        return decode_erc20_approval(log).map(DecodedEvent::UsdcApproval);
+    }
+    if let Some(event) = route_by_topic(log) {
+        return Some(event);
+    }
+    if log.address == network.ctf {
+        return decode_ctf_events(log).map(DecodedEvent::Ctf);
+    }
+
+    None
+}
+
+// Events matched on topic0 alone: token standards, FPMM markets (deployed at runtime) and the UMA adapter
+pub fn route_by_topic(log: &Log) -> Option<DecodedEvent> {
+    let topic0: [u8; 32] = log.topics.first()?.as_slice().try_into().ok()?;
+
+    match topic0 {
+        TRANSFER_SINGLE_SIG => decode_erc1155_transfer_single(log).map(DecodedEvent::Erc1155TransferSingle),
+        TRANSFER_BATCH_SIG => decode_erc1155_transfer_batch(log).map(DecodedEvent::Erc1155TransferBatch),
+        ERC20_TRANSFER_SIG => decode_erc20_transfer(log).map(DecodedEvent::Erc20Transfer),
+        FPMM_BUY_SIG | FPMM_SELL_SIG => decode_fpmm_trade(log).map(DecodedEvent::FpmmTrade),
+        QUESTION_INITIALIZED_SIG => decode_question_initialized(log).map(DecodedEvent::QuestionInitialized),
+        _ => None,
+    }
+}
+
+// The event route() reads a log as, by the same (address, topic0) dispatch but without decoding, so a known
+// event that route() returns None for failed to decode
+pub fn event_type(network: &NetworkProfile, log: &Log) -> Option<&'static str> {
+    let topic0: [u8; 32] = log.topics.first()?.as_slice().try_into().ok()?;
+
+    if network.is_exchange(&log.address) {
+        return match topic0 {
+            TOKEN_REGISTERED_SIG => Some("TokenRegistered"),
+            ORDER_FILLED_SIG => Some("OrderFilled"),
+            ORDERS_MATCHED_SIG => Some("OrdersMatched"),
+            ORDER_CANCELLED_SIG => Some("OrderCancelled"),
+            FEE_CHARGED_SIG => Some("FeeCharged"),
+            TRADING_PAUSED_SIG => Some("TradingPaused"),
+            TRADING_UNPAUSED_SIG => Some("TradingUnpaused"),
+            NEW_ADMIN_SIG => Some("NewAdmin"),
+            NEW_OPERATOR_SIG => Some("NewOperator"),
+            REMOVED_ADMIN_SIG => Some("RemovedAdmin"),
+            REMOVED_OPERATOR_SIG => Some("RemovedOperator"),
+            PROXY_FACTORY_UPDATED_SIG => Some("ProxyFactoryUpdated"),
+            SAFE_FACTORY_UPDATED_SIG => Some("SafeFactoryUpdated"),
+            _ => None,
+        };
+    }
+    if log.address == network.fpmm_factory {
+        return (topic0 == FPMM_CREATION_SIG).then_some("FixedProductMarketMakerCreation");
+    }
+    if network.is_merkle_distributor(&log.address) && topic0 == CLAIMED_SIG {
+        return Some("Claimed");
+    }
+    if log.address == network.usdc && topic0 == ERC20_APPROVAL_SIG {
+        return Some("Approval");
+    }
+
+    match topic0 {
+        TRANSFER_SINGLE_SIG => Some("TransferSingle"),
+        TRANSFER_BATCH_SIG => Some("TransferBatch"),
+        // ERC721 shares the ERC20 topic0 with the token id as a 4th topic, those are not ERC20 Transfers
+        ERC20_TRANSFER_SIG if log.topics.len() == 3 => Some("Transfer"),
+        FPMM_BUY_SIG => Some("FPMMBuy"),
+        FPMM_SELL_SIG => Some("FPMMSell"),
+        QUESTION_INITIALIZED_SIG => Some("QuestionInitialized"),
+        CONDITION_PREPARATION_SIG if log.address == network.ctf => Some("ConditionPreparation"),
+        CONDITION_RESOLUTION_SIG if log.address == network.ctf => Some("ConditionResolution"),
+        POSITION_SPLIT_SIG if log.address == network.ctf => Some("PositionSplit"),
+        POSITIONS_MERGE_SIG if log.address == network.ctf => Some("PositionsMerge"),
+        PAYOUT_REDEMPTION_SIG if log.address == network.ctf => Some("PayoutRedemption"),
+        _ => None,
     }
 }
 
@@ -20,95 +184,133 @@ fn u256_from_bytes(bytes: &[u8]) -> u64 {
 
 pub fn decode_token_registered(log: &Log) -> Option<contract::TokenRegistered> {
     // Decode TokenRegistered event from CTF Exchange
-    // Event signature: TokenRegistered(bytes32 indexed conditionId, address indexed token0, address indexed token1)
-    if log.topics.len() >= 4 {
+    // Event signature: TokenRegistered(uint256 indexed token0, uint256 indexed token1, bytes32 indexed conditionId)
+    if log.topics.len() >= 4 && log.topics[0] == TOKEN_REGISTERED_SIG {
         Some(contract::TokenRegistered {
             evt_tx_hash: "0x".to_string(), // Will be set by caller
             evt_index: log.block_index,
             evt_block_time: None, // Will be set by caller
             evt_block_number: 0, // Will be set by caller
-            condition_id: log.topics[1].to_vec(),
-            token0: Hex(&log.topics[2]).to_string(),
-            token1: Hex(&log.topics[3]).to_string(),
+            evt_tx_index: 0, // Will be set by caller
+            evt_tx_from: Vec::new(), // Will be set by caller
+            condition_id: log.topics[3].to_vec(),
+            token0: u256_from_bytes(&log.topics[1]).to_string(),
+            token1: u256_from_bytes(&log.topics[2]).to_string(),
         })
     } else {
         None
     }
 }
 
-pub fn decode_neg_risk_token_registered(log: &Log) -> Option<contract::NegRiskTokenRegistered> {
-    // Decode NegRisk TokenRegistered event
-    if log.topics.len() >= 4 {
-        Some(contract::NegRiskTokenRegistered {
-            evt_tx_hash: "0x".to_string(),
-            evt_index: log.block_index,
-            evt_block_time: None,
-            evt_block_number: 0,
-            condition_id: log.topics[1].to_vec(),
-            token0: Hex(&log.topics[2]).to_string(),
-            token1: Hex(&log.topics[3]).to_string(),
-            is_neg_risk: true,
-            is_augmented: true,
-            event_id: "0x".to_string(), // Would need to decode from data
-        })
-    } else {
-        None
+// NegRisk exchange registrations use the same event, recorded with the NegRisk flags
+pub fn neg_risk_token_registered(registered: contract::TokenRegistered) -> contract::NegRiskTokenRegistered {
+    contract::NegRiskTokenRegistered {
+        evt_tx_hash: registered.evt_tx_hash,
+        evt_index: registered.evt_index,
+        evt_block_time: registered.evt_block_time,
+        evt_block_number: registered.evt_block_number,
+        evt_tx_index: registered.evt_tx_index,
+        evt_tx_from: registered.evt_tx_from,
+        condition_id: registered.condition_id,
+        token0: registered.token0,
+        token1: registered.token1,
+        is_neg_risk: true,
+        is_augmented: true,
+        event_id: "0x".to_string(), // Would need to decode from data
     }
 }
 
 pub fn decode_fpmm_creation(log: &Log) -> Option<contract::FpmmFactoryCreation> {
     // Decode FixedProductMarketMakerCreation event
-    // Event signature: FixedProductMarketMakerCreation(address indexed fixedProductMarketMaker, address indexed creator, bytes32[] conditionIds, address collateralToken, uint256 fee, uint256 endTime, bytes32 questionId)
-    if log.topics.len() >= 3 {
-        Some(contract::FpmmFactoryCreation {
-            evt_tx_hash: "0x".to_string(),
-            evt_index: log.block_index,
-            evt_block_time: None,
-            evt_block_number: 0,
-            fixed_product_market_maker: log.topics[1].to_vec(),
-            creator: log.topics[2].to_vec(),
-            condition_ids: Vec::new(), // Would need to decode from data
-            collateral_token: Vec::new(), // Would need to decode from data
-            fee: "0".to_string(), // Would need to decode from data
-            end_time: "0".to_string(), // Would need to decode from data
-            question_id: "0x".to_string(), // Would need to decode from data
-        })
-    } else {
-        None
+    // Event signature: FixedProductMarketMakerCreation(address indexed creator, address fixedProductMarketMaker, address indexed conditionalTokens, address indexed collateralToken, bytes32[] conditionIds, uint256 fee)
+    if log.topics.len() < 4 || log.topics[0] != FPMM_CREATION_SIG {
+        return None;
     }
+
+    let mut values = ethabi::decode(
+        &[
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Array(Box::new(ethabi::ParamType::FixedBytes(32))),
+            ethabi::ParamType::Uint(256),
+        ],
+        &log.data,
+    ).ok()?;
+    let fee = values.pop()?.into_uint()?;
+    let condition_ids = values.pop()?.into_array()?;
+    let fixed_product_market_maker = values.pop()?.into_address()?;
+
+    Some(contract::FpmmFactoryCreation {
+        evt_tx_hash: "0x".to_string(),
+        evt_index: log.block_index,
+        evt_block_time: None,
+        evt_block_number: 0,
+        evt_tx_index: 0,
+        evt_tx_from: Vec::new(),
+        fixed_product_market_maker: fixed_product_market_maker.as_bytes().to_vec(),
+        creator: log.topics[1][12..].to_vec(),
+        condition_ids: condition_ids.into_iter().filter_map(|id| id.into_fixed_bytes()).collect(),
+        collateral_token: log.topics[3][12..].to_vec(),
+        fee: fee.to_string(),
+        end_time: "0".to_string(), // Not part of the event
+        question_id: "0x".to_string(), // Not part of the event
+    })
 }
 
 pub fn decode_order_filled(log: &Log) -> Option<contract::OrderFilled> {
     // Decode OrderFilled event
-    // Event signature: OrderFilled(address indexed maker, address indexed taker, uint256 makerAssetId, uint256 takerAssetId, uint256 makerAmountFilled, uint256 takerAmountFilled, uint256 fee, bytes32 orderHash)
-    if log.topics.len() >= 4 && log.data.len() >= 192 { // 6 * 32 bytes
-        // Decode the data: 6 uint256 values (192 bytes total)
+    // Event signature: OrderFilled(bytes32 indexed orderHash, address indexed maker, address indexed taker, uint256 makerAssetId, uint256 takerAssetId, uint256 makerAmountFilled, uint256 takerAmountFilled, uint256 fee)
+    if log.topics.len() >= 4 && log.topics[0] == ORDER_FILLED_SIG && log.data.len() >= 160 { // 5 * 32 bytes
+        // Decode the data: 5 uint256 values (160 bytes total)
         let maker_asset_id = u256_from_bytes(&log.data[0..32]);
         let taker_asset_id = u256_from_bytes(&log.data[32..64]);
         let maker_amount_filled = u256_from_bytes(&log.data[64..96]);
         let taker_amount_filled = u256_from_bytes(&log.data[96..128]);
         let fee = u256_from_bytes(&log.data[128..160]);
-        // orderHash is in topics[3]
-        
+
         Some(contract::OrderFilled {
             evt_tx_hash: "0x".to_string(),
             evt_index: log.block_index,
             evt_block_time: None,
             evt_block_number: 0,
-            maker: log.topics[1].to_vec(),
-            taker: log.topics[2].to_vec(),
+            evt_tx_index: 0,
+            evt_tx_from: Vec::new(),
+            maker: log.topics[2][12..].to_vec(),
+            taker: log.topics[3][12..].to_vec(),
             maker_asset_id: maker_asset_id.to_string(),
             taker_asset_id: taker_asset_id.to_string(),
             maker_amount_filled: maker_amount_filled.to_string(),
             taker_amount_filled: taker_amount_filled.to_string(),
             fee: fee.to_string(),
-            order_hash: log.topics[3].to_vec(),
+            order_hash: log.topics[1].to_vec(),
         })
     } else {
         None
     }
 }
 
+pub fn decode_orders_matched(log: &Log) -> Option<contract::OrdersMatched> {
+    // Decode OrdersMatched event
+    // Event signature: OrdersMatched(bytes32 indexed takerOrderHash, address indexed takerOrderMaker, uint256 makerAssetId, uint256 takerAssetId, uint256 makerAmountFilled, uint256 takerAmountFilled)
+    if log.topics.len() < 3 || log.topics[0] != ORDERS_MATCHED_SIG || log.data.len() < 128 {
+        return None;
+    }
+
+    Some(contract::OrdersMatched {
+        evt_tx_hash: "0x".to_string(),
+        evt_index: log.block_index,
+        evt_block_time: None,
+        evt_block_number: 0,
+        evt_tx_index: 0,
+        evt_tx_from: Vec::new(),
+        taker_order_hash: log.topics[1].to_vec(),
+        taker_order_maker: log.topics[2][12..].to_vec(),
+        maker_asset_id: u256_from_bytes(&log.data[0..32]).to_string(),
+        taker_asset_id: u256_from_bytes(&log.data[32..64]).to_string(),
+        maker_amount_filled: u256_from_bytes(&log.data[64..96]).to_string(),
+        taker_amount_filled: u256_from_bytes(&log.data[96..128]).to_string(),
+    })
+}
+
 pub fn decode_erc1155_transfer_single(log: &Log) -> Option<contract::Erc1155TransferSingle> {
     // Decode ERC1155 TransferSingle event
     // Event signature: TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value)
@@ -125,10 +327,12 @@ pub fn decode_erc1155_transfer_single(log: &Log) -> Option<contract::Erc1155Tran
             evt_index: log.block_index,
             evt_block_time: None,
             evt_block_number: 0,
+            evt_tx_index: 0,
+            evt_tx_from: Vec::new(),
             contract_address: log.address.to_vec(),
-            operator: log.topics[1].to_vec(),
-            from: log.topics[2].to_vec(),
-            to: log.topics[3].to_vec(),
+            operator: log.topics[1][12..].to_vec(),
+            from: log.topics[2][12..].to_vec(),
+            to: log.topics[3][12..].to_vec(),
             id: id.to_string(),
             value: value.to_string(),
         })
@@ -140,28 +344,39 @@ pub fn decode_erc1155_transfer_single(log: &Log) -> Option<contract::Erc1155Tran
 pub fn decode_erc1155_transfer_batch(log: &Log) -> Option<contract::Erc1155TransferBatch> {
     // Decode ERC1155 TransferBatch event
     // Event signature: TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values)
-    if log.topics.len() >= 4 {
-        Some(contract::Erc1155TransferBatch {
-            evt_tx_hash: "0x".to_string(),
-            evt_index: log.block_index,
-            evt_block_time: None,
-            evt_block_number: 0,
-            contract_address: log.address.to_vec(),
-            operator: log.topics[1].to_vec(),
-            from: log.topics[2].to_vec(),
-            to: log.topics[3].to_vec(),
-            ids: Vec::new(), // Would need to decode from data
-            values: Vec::new(), // Would need to decode from data
-        })
-    } else {
-        None
+    if log.topics.len() < 4 {
+        return None;
     }
+
+    let uint_array = || ethabi::ParamType::Array(Box::new(ethabi::ParamType::Uint(256)));
+    let mut arrays = ethabi::decode(&[uint_array(), uint_array()], &log.data).ok()?;
+    let values = arrays.pop()?.into_array()?;
+    let ids = arrays.pop()?.into_array()?;
+    let to_strings = |tokens: Vec<ethabi::Token>| -> Option<Vec<String>> {
+        tokens.into_iter().map(|token| token.into_uint().map(|value| value.to_string())).collect()
+    };
+
+    Some(contract::Erc1155TransferBatch {
+        evt_tx_hash: "0x".to_string(),
+        evt_index: log.block_index,
+        evt_block_time: None,
+        evt_block_number: 0,
+        evt_tx_index: 0,
+        evt_tx_from: Vec::new(),
+        contract_address: log.address.to_vec(),
+        operator: log.topics[1][12..].to_vec(),
+        from: log.topics[2][12..].to_vec(),
+        to: log.topics[3][12..].to_vec(),
+        ids: to_strings(ids)?,
+        values: to_strings(values)?,
+    })
 }
 
 pub fn decode_erc20_transfer(log: &Log) -> Option<contract::Erc20Transfer> {
     // Decode ERC20 Transfer event
     // Event signature: Transfer(address indexed from, address indexed to, uint256 value)
-    if log.topics.len() >= 3 && log.data.len() >= 32 {
+    // Exactly 3 topics: ERC721 Transfers index the token id as a 4th
+    if log.topics.len() == 3 && log.data.len() >= 32 {
         // Decode the data: uint256 value (32 bytes)
         let value_bytes = &log.data[0..32];
         let value = u256_from_bytes(value_bytes);
@@ -171,9 +386,11 @@ pub fn decode_erc20_transfer(log: &Log) -> Option<contract::Erc20Transfer> {
             evt_index: log.block_index,
             evt_block_time: None,
             evt_block_number: 0,
+            evt_tx_index: 0,
+            evt_tx_from: Vec::new(),
             contract_address: log.address.to_vec(),
-            from: log.topics[1].to_vec(),
-            to: log.topics[2].to_vec(),
+            from: log.topics[1][12..].to_vec(),
+            to: log.topics[2][12..].to_vec(),
             value: value.to_string(),
         })
     } else {
@@ -183,20 +400,22 @@ pub fn decode_erc20_transfer(log: &Log) -> Option<contract::Erc20Transfer> {
 
 pub fn decode_merkle_claimed(log: &Log) -> Option<contract::MerkleDistributorClaimed> {
     // Decode MerkleDistributor Claimed event
-    // Event signature: Claimed(address indexed airdropRecipient, uint256 amount)
-    if log.topics.len() >= 3 {
-        Some(contract::MerkleDistributorClaimed {
-            evt_tx_hash: "0x".to_string(),
-            evt_index: log.block_index,
-            evt_block_time: None,
-            evt_block_number: 0,
-            airdrop_recipient: log.topics[1].to_vec(),
-            amount: "0".to_string(), // Would need to decode from data
-            token_address: Hex(&log.address).to_string(),
-        })
-    } else {
-        None
+    // Event signature: Claimed(address indexed airdropRecipient, uint256 amount), the only layout the distributors emit
+    if log.topics.len() != 2 || log.data.len() < 32 {
+        return None;
     }
+
+    Some(contract::MerkleDistributorClaimed {
+        evt_tx_hash: "0x".to_string(),
+        evt_index: log.block_index,
+        evt_block_time: None,
+        evt_block_number: 0,
+        evt_tx_index: 0,
+        evt_tx_from: Vec::new(),
+        airdrop_recipient: log.topics[1][12..].to_vec(),
+        amount: u256_from_bytes(&log.data[0..32]).to_string(),
+        token_address: Hex(&log.address).to_string(),
+    })
 }
 
 // CTF Event Types
@@ -206,248 +425,315 @@ pub enum CtfEventType {
     PositionSplit(contract::CtfPositionSplit),
     PositionMerge(contract::CtfPositionMerge),
     PositionRedeem(contract::CtfPositionRedeem),
-    TransferSingle(contract::CtfTransferSingle),
-    TransferBatch(contract::CtfTransferBatch),
 }
 
 pub fn decode_ctf_events(log: &Log) -> Option<CtfEventType> {
-    // Decode various CTF events based on event signature
-    // This is a simplified version - in production you'd decode based on actual event signatures
-    if log.topics.len() >= 2 {
-        // For now, return a placeholder TransferSingle event
-        Some(CtfEventType::TransferSingle(contract::CtfTransferSingle {
+    // Decode CTF events by event signature using the generated ConditionalTokens bindings (transfers are routed as ERC1155)
+    if let Some(evt) = ctf::ConditionPreparation::match_and_decode(log) {
+        return Some(CtfEventType::ConditionPreparation(contract::CtfConditionPreparation {
             evt_tx_hash: "0x".to_string(),
             evt_index: log.block_index,
             evt_block_time: None,
             evt_block_number: 0,
-            operator: log.topics[1].to_vec(),
-            from: Vec::new(),
-            to: Vec::new(),
-            token_id: "0".to_string(),
-            value: "0".to_string(),
-        }))
-    } else {
-        None
+            evt_tx_index: 0,
+            evt_tx_from: Vec::new(),
+            condition_id: evt.condition_id.to_vec(),
+            oracle: evt.oracle,
+            question_id: evt.question_id.to_vec(),
+            outcome_slot_count: slot_count(evt.outcome_slot_count),
+        }));
     }
-}
 
-// USDC Event Types
-pub enum UsdcEventType {
-    Transfer(contract::UsdcTransfer),
-    Approval(contract::UsdcApproval),
-}
+    if let Some(evt) = ctf::ConditionResolution::match_and_decode(log) {
+        // The CTF payout denominator is the sum of the reported numerators
+        let payout_denominator = evt.payout_numerators.iter()
+            .fold(BigInt::zero(), |acc, n| acc + n.clone());
+        return Some(CtfEventType::ConditionResolution(contract::CtfConditionResolution {
+            evt_tx_hash: "0x".to_string(),
+            evt_index: log.block_index,
+            evt_block_time: None,
+            evt_block_number: 0,
+            evt_tx_index: 0,
+            evt_tx_from: Vec::new(),
+            condition_id: evt.condition_id.to_vec(),
+            oracle: evt.oracle,
+            question_id: evt.question_id.to_vec(),
+            outcome_slot_count: slot_count(evt.outcome_slot_count),
+            payout_numerators: evt.payout_numerators.iter().map(|n| n.to_string()).collect(),
+            payout_denominator: payout_denominator.to_string(),
+        }));
+    }
 
-pub fn decode_usdc_events(log: &Log) -> Option<UsdcEventType> {
-    // Decode USDC Transfer and Approval events
-    if log.topics.len() >= 3 {
-        // For now, return a placeholder Transfer event
-        Some(UsdcEventType::Transfer(contract::UsdcTransfer {
+    if let Some(evt) = ctf::PositionSplit::match_and_decode(log) {
+        return Some(CtfEventType::PositionSplit(contract::CtfPositionSplit {
             evt_tx_hash: "0x".to_string(),
             evt_index: log.block_index,
             evt_block_time: None,
             evt_block_number: 0,
-            from: log.topics[1].to_vec(),
-            to: log.topics[2].to_vec(),
-            value: "0".to_string(),
-        }))
-    } else {
-        None
+            evt_tx_index: 0,
+            evt_tx_from: Vec::new(),
+            stakeholder: evt.stakeholder,
+            collateral_token: evt.collateral_token,
+            parent_collection_id: evt.parent_collection_id.to_vec(),
+            condition_id: evt.condition_id.to_vec(),
+            partition: evt.partition.iter().map(|p| p.to_string()).collect(),
+            amount: evt.amount.to_string(),
+        }));
+    }
+
+    if let Some(evt) = ctf::PositionsMerge::match_and_decode(log) {
+        return Some(CtfEventType::PositionMerge(contract::CtfPositionMerge {
+            evt_tx_hash: "0x".to_string(),
+            evt_index: log.block_index,
+            evt_block_time: None,
+            evt_block_number: 0,
+            evt_tx_index: 0,
+            evt_tx_from: Vec::new(),
+            stakeholder: evt.stakeholder,
+            collateral_token: evt.collateral_token,
+            parent_collection_id: evt.parent_collection_id.to_vec(),
+            condition_id: evt.condition_id.to_vec(),
+            partition: evt.partition.iter().map(|p| p.to_string()).collect(),
+            amount: evt.amount.to_string(),
+        }));
+    }
+
+    if let Some(evt) = ctf::PayoutRedemption::match_and_decode(log) {
+        return Some(CtfEventType::PositionRedeem(contract::CtfPositionRedeem {
+            evt_tx_hash: "0x".to_string(),
+            evt_index: log.block_index,
+            evt_block_time: None,
+            evt_block_number: 0,
+            evt_tx_index: 0,
+            evt_tx_from: Vec::new(),
+            redeemer: evt.redeemer,
+            collateral_token: evt.collateral_token,
+            parent_collection_id: evt.parent_collection_id.to_vec(),
+            condition_id: evt.condition_id.to_vec(),
+            index_sets: evt.index_sets.iter().map(|i| i.to_string()).collect(),
+            payout: evt.payout.to_string(),
+        }));
     }
+
+    None
 }
 
-pub fn decode_erc1155_transfer_batch_ctf(log: &Log) -> Option<contract::CtfTransferBatch> {
-    if log.topics.len() < 4 {
+pub fn decode_erc20_approval(log: &Log) -> Option<contract::UsdcApproval> {
+    // Decode ERC20 Approval event
+    // Event signature: Approval(address indexed owner, address indexed spender, uint256 value)
+    if log.topics.len() < 3 || log.topics[0] != ERC20_APPROVAL_SIG || log.data.len() < 32 {
         return None;
     }
-    
-    // Decode arrays from log.data
-    let mut token_ids = Vec::new();
-    let mut values = Vec::new();
-    
-    // Skip the first 32 bytes (array offset), then read array length
-    if log.data.len() >= 64 {
-        let array_length = u256_from_bytes(&log.data[32..64]) as usize;
-        
-        // Read each ID and value (32 bytes each)
-        for i in 0..array_length {
-            let id_offset = 64 + (i * 32);
-            let value_offset = 64 + (array_length * 32) + (i * 32);
-            
-            if id_offset + 32 <= log.data.len() && value_offset + 32 <= log.data.len() {
-                let id = u256_from_bytes(&log.data[id_offset..id_offset + 32]);
-                let value = u256_from_bytes(&log.data[value_offset..value_offset + 32]);
-                token_ids.push(id.to_string());
-                values.push(value.to_string());
-            }
-        }
-    }
-    
-    Some(contract::CtfTransferBatch {
+
+    Some(contract::UsdcApproval {
         evt_tx_hash: "0x".to_string(),
-        evt_index: 0,
+        evt_index: log.block_index,
         evt_block_time: None,
         evt_block_number: 0,
-        operator: Vec::new(),
-        from: Vec::new(),
-        to: Vec::new(),
-        token_ids,
-        values,
+        evt_tx_index: 0,
+        evt_tx_from: Vec::new(),
+        owner: log.topics[1][12..].to_vec(),
+        spender: log.topics[2][12..].to_vec(),
+        value: u256_from_bytes(&log.data[0..32]).to_string(),
     })
 }
 
-pub fn decode_fixed_product_market_maker_creation(log: &Log) -> Option<String> {
+// Exchange Event Types
+pub enum ExchangeEventType {
+    OrderCancelled(contract::ExchangeOrderCancelled),
+    FeeCharged(contract::ExchangeFeeCharged),
+    TradingPaused(contract::ExchangeTradingPaused),
+    RoleChange(contract::ExchangeRoleChange),
+    FactoryUpdate(contract::ExchangeFactoryUpdate),
+}
+
+pub fn decode_exchange_events(log: &Log) -> Option<ExchangeEventType> {
+    // Event signatures:
+    // OrderCancelled(bytes32 indexed orderHash)
+    // FeeCharged(address indexed receiver, uint256 tokenId, uint256 amount)
+    // TradingPaused(address indexed pauser) / TradingUnpaused(address indexed pauser)
+    // NewAdmin/RemovedAdmin/NewOperator/RemovedOperator(address indexed account, address indexed admin)
+    // ProxyFactoryUpdated/SafeFactoryUpdated(address indexed oldFactory, address indexed newFactory)
     if log.topics.len() < 2 {
         return None;
     }
-    
-    // Decode the fixedProductMarketMaker address from topics[1]
-    let fixed_product_market_maker = format!("0x{}", substreams::Hex(&log.topics[1]).to_string());
-    
-    Some(fixed_product_market_maker)
-}
+    let topic0 = log.topics[0].as_slice();
 
-pub fn decode_uma_merkle_claim(log: &Log) -> Option<contract::DuneRewardClaim> {
-    if log.topics.len() < 2 {
+    if topic0 == ORDER_CANCELLED_SIG {
+        return Some(ExchangeEventType::OrderCancelled(contract::ExchangeOrderCancelled {
+            evt_tx_hash: "0x".to_string(),
+            evt_index: log.block_index,
+            evt_block_time: None,
+            evt_block_number: 0,
+            evt_tx_index: 0,
+            evt_tx_from: Vec::new(),
+            exchange: log.address.clone(),
+            order_hash: log.topics[1].to_vec(),
+        }));
+    }
+
+    if topic0 == FEE_CHARGED_SIG {
+        if log.data.len() < 64 {
+            return None;
+        }
+        return Some(ExchangeEventType::FeeCharged(contract::ExchangeFeeCharged {
+            evt_tx_hash: "0x".to_string(),
+            evt_index: log.block_index,
+            evt_block_time: None,
+            evt_block_number: 0,
+            evt_tx_index: 0,
+            evt_tx_from: Vec::new(),
+            exchange: log.address.clone(),
+            receiver: log.topics[1][12..].to_vec(),
+            token_id: u256_from_bytes(&log.data[0..32]).to_string(),
+            amount: u256_from_bytes(&log.data[32..64]).to_string(),
+        }));
+    }
+
+    if topic0 == TRADING_PAUSED_SIG || topic0 == TRADING_UNPAUSED_SIG {
+        return Some(ExchangeEventType::TradingPaused(contract::ExchangeTradingPaused {
+            evt_tx_hash: "0x".to_string(),
+            evt_index: log.block_index,
+            evt_block_time: None,
+            evt_block_number: 0,
+            evt_tx_index: 0,
+            evt_tx_from: Vec::new(),
+            exchange: log.address.clone(),
+            pauser: log.topics[1][12..].to_vec(),
+            paused: topic0 == TRADING_PAUSED_SIG,
+        }));
+    }
+
+    if log.topics.len() < 3 {
         return None;
     }
-    
-    // Decode the airdropRecipient address from topics[1]
-    let airdrop_recipient = format!("0x{}", substreams::Hex(&log.topics[1]).to_string());
-    
-    // Decode amount from log.data
-    let amount = if log.data.len() >= 32 {
-        u256_from_bytes(&log.data[0..32]).to_string()
-    } else {
-        "0".to_string()
+
+    let role_change = match topic0 {
+        t if t == NEW_ADMIN_SIG => Some(("ADMIN", true)),
+        t if t == REMOVED_ADMIN_SIG => Some(("ADMIN", false)),
+        t if t == NEW_OPERATOR_SIG => Some(("OPERATOR", true)),
+        t if t == REMOVED_OPERATOR_SIG => Some(("OPERATOR", false)),
+        _ => None,
     };
-    
-    // Convert to UMA units (divide by 10^18) as per Dune query
-    let amount_uma = if let Ok(val) = amount.parse::<f64>() {
-        (val / 1e18).to_string()
+    if let Some((role, granted)) = role_change {
+        return Some(ExchangeEventType::RoleChange(contract::ExchangeRoleChange {
+            evt_tx_hash: "0x".to_string(),
+            evt_index: log.block_index,
+            evt_block_time: None,
+            evt_block_number: 0,
+            evt_tx_index: 0,
+            evt_tx_from: Vec::new(),
+            exchange: log.address.clone(),
+            role: role.to_string(),
+            account: log.topics[1][12..].to_vec(),
+            admin: log.topics[2][12..].to_vec(),
+            granted,
+        }));
+    }
+
+    let factory = if topic0 == PROXY_FACTORY_UPDATED_SIG {
+        "PROXY"
+    } else if topic0 == SAFE_FACTORY_UPDATED_SIG {
+        "SAFE"
     } else {
-        "0".to_string()
+        return None;
     };
-    
-    // For now, use amount as USD amount (would need price data for accurate conversion)
-    let usd_amount = amount_uma.clone();
-    
-    Some(contract::DuneRewardClaim {
-        transaction_hash: "0x".to_string(),
-        log_index: 0,
-        block_timestamp: None,
-        block_number: 0,
-        airdrop_recipient,
-        asset: "uma".to_string(),
-        lc_amount: amount_uma,
-        usd_amount,
-        token_address: "0x".to_string(),
-    })
+    Some(ExchangeEventType::FactoryUpdate(contract::ExchangeFactoryUpdate {
+        evt_tx_hash: "0x".to_string(),
+        evt_index: log.block_index,
+        evt_block_time: None,
+        evt_block_number: 0,
+        evt_tx_index: 0,
+        evt_tx_from: Vec::new(),
+        exchange: log.address.clone(),
+        factory: factory.to_string(),
+        old_factory: log.topics[1][12..].to_vec(),
+        new_factory: log.topics[2][12..].to_vec(),
+    }))
 }
 
-pub fn decode_usdc_merkle_claim(log: &Log) -> Option<contract::DuneRewardClaim> {
-    if log.topics.len() < 2 {
+// FPMM trade direction
+pub enum FpmmTradeSide {
+    Buy,
+    Sell,
+}
+
+// Helper struct for FPMMBuy/FPMMSell data
+pub struct FpmmTradeData {
+    pub side: FpmmTradeSide,
+    pub trader: Vec<u8>,
+    pub outcome_index: u32,
+    pub collateral_amount: BigInt, // investmentAmount (buy) or returnAmount (sell)
+    pub fee_amount: BigInt,
+    pub outcome_tokens: BigInt,    // outcomeTokensBought (buy) or outcomeTokensSold (sell)
+}
+
+pub fn decode_fpmm_trade(log: &Log) -> Option<FpmmTradeData> {
+    // Event signatures:
+    // FPMMBuy(address indexed buyer, uint256 investmentAmount, uint256 feeAmount, uint256 indexed outcomeIndex, uint256 outcomeTokensBought)
+    // FPMMSell(address indexed seller, uint256 returnAmount, uint256 feeAmount, uint256 indexed outcomeIndex, uint256 outcomeTokensSold)
+    if log.topics.len() < 3 || log.data.len() < 96 {
         return None;
     }
-    
-    // Decode the airdropRecipient address from topics[1]
-    let airdrop_recipient = format!("0x{}", substreams::Hex(&log.topics[1]).to_string());
-    
-    // Decode amount from log.data
-    let amount = if log.data.len() >= 32 {
-        u256_from_bytes(&log.data[0..32]).to_string()
-    } else {
-        "0".to_string()
-    };
-    
-    // Convert to USDC units (divide by 1000000) as per Dune query
-    let amount_usdc = if let Ok(val) = amount.parse::<f64>() {
-        (val / 1_000_000.0).to_string()
+
+    let side = if log.topics[0] == FPMM_BUY_SIG {
+        FpmmTradeSide::Buy
+    } else if log.topics[0] == FPMM_SELL_SIG {
+        FpmmTradeSide::Sell
     } else {
-        "0".to_string()
+        return None;
     };
-    
-    Some(contract::DuneRewardClaim {
-        transaction_hash: "0x".to_string(),
-        log_index: 0,
-        block_timestamp: None,
-        block_number: 0,
-        airdrop_recipient,
-        asset: "usdc".to_string(),
-        lc_amount: amount_usdc.clone(),
-        usd_amount: amount_usdc,
-        token_address: "0x".to_string(),
+
+    Some(FpmmTradeData {
+        side,
+        trader: log.topics[1][12..].to_vec(),
+        outcome_index: u256_to_usize(&log.topics[2]).min(u32::MAX as usize) as u32,
+        collateral_amount: u256_from_bytes(&log.data[0..32]),
+        fee_amount: u256_from_bytes(&log.data[32..64]),
+        outcome_tokens: u256_from_bytes(&log.data[64..96]),
     })
 }
 
 pub fn decode_question_initialized(log: &Log) -> Option<QuestionInitializedData> {
+    // Event data: requestTimestamp(uint256), creator(address), ancillaryData(bytes), question(string), rewardToken(address), reward(uint256), proposalBond(uint256)
     if log.topics.len() < 2 {
         return None;
     }
-    
-    // Decode questionID from topics[1] (bytes32)
-    let question_id = format!("0x{}", substreams::Hex(&log.topics[1]).to_string());
-    
-    // Decode other parameters from log.data
-    // The data structure is: requestTimestamp(uint256), creator(address), ancillaryData(bytes), question(string), rewardToken(address), reward(uint256), proposalBond(uint256)
-    let mut offset = 0;
-    
-    // requestTimestamp (uint256) - 32 bytes
-    let request_timestamp = if log.data.len() >= offset + 32 {
-        u256_from_bytes(&log.data[offset..offset + 32]).to_string()
-    } else {
-        "0".to_string()
-    };
-    offset += 32;
-    
-    // creator (address) - 32 bytes (padded)
-    let creator = if log.data.len() >= offset + 32 {
-        format!("0x{}", substreams::Hex(&log.data[offset + 12..offset + 32]).to_string())
-    } else {
-        "0x".to_string()
-    };
-    offset += 32;
-    
-    // ancillaryData (bytes) - dynamic, skip for now
-    // question (string) - dynamic, skip for now
-    // rewardToken (address) - 32 bytes (padded)
-    let reward_token = if log.data.len() >= offset + 32 {
-        format!("0x{}", substreams::Hex(&log.data[offset + 12..offset + 32]).to_string())
-    } else {
-        "0x".to_string()
-    };
-    offset += 32;
-    
-    // reward (uint256) - 32 bytes
-    let reward = if log.data.len() >= offset + 32 {
-        u256_from_bytes(&log.data[offset..offset + 32]).to_string()
-    } else {
-        "0".to_string()
-    };
-    offset += 32;
-    
-    // proposalBond (uint256) - 32 bytes
-    let proposal_bond = if log.data.len() >= offset + 32 {
-        u256_from_bytes(&log.data[offset..offset + 32]).to_string()
-    } else {
-        "0".to_string()
-    };
-    
-    // For now, use a placeholder question - in a real implementation, you'd decode the dynamic string
-    let question = format!("Question for {}", question_id);
-    
+
+    let mut values = ethabi::decode(
+        &[
+            ethabi::ParamType::Uint(256),
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Bytes,
+            ethabi::ParamType::String,
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Uint(256),
+            ethabi::ParamType::Uint(256),
+        ],
+        &log.data,
+    ).ok()?;
+    let proposal_bond = values.pop()?.into_uint()?;
+    let reward = values.pop()?.into_uint()?;
+    let reward_token = values.pop()?.into_address()?;
+    let question = values.pop()?.into_string()?;
+    let ancillary_data = values.pop()?.into_bytes()?;
+    let creator = values.pop()?.into_address()?;
+    let request_timestamp = values.pop()?.into_uint()?;
+
     Some(QuestionInitializedData {
-        question_id,
-        request_timestamp,
-        creator,
-        ancillary_data: "0x".to_string(),
+        question_id: format!("0x{}", Hex(&log.topics[1])),
+        request_timestamp: request_timestamp.to_string(),
+        creator: format!("0x{}", Hex(creator.as_bytes())),
+        ancillary_data: format!("0x{}", Hex(&ancillary_data)),
         question,
-        reward_token,
-        reward,
-        proposal_bond,
+        reward_token: format!("0x{}", Hex(reward_token.as_bytes())),
+        reward: reward.to_string(),
+        proposal_bond: proposal_bond.to_string(),
     })
 }
 
 // Helper struct for QuestionInitialized data
+#[allow(dead_code)]
 pub struct QuestionInitializedData {
     pub question_id: String,
     pub request_timestamp: String,
@@ -458,3 +744,609 @@ pub struct QuestionInitializedData {
     pub reward: String,
     pub proposal_bond: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethabi::{Address, ParamType, Token, Uint};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    // Random events per round-trip property, from a fixed seed so failures reproduce
+    const CASES: usize = 64;
+
+    fn rng() -> StdRng {
+        StdRng::seed_from_u64(0xdec0de)
+    }
+
+    // Mostly full 256-bit words, with zero, the maximum and small values mixed in
+    fn uint(rng: &mut StdRng) -> Uint {
+        match rng.gen_range(0..8) {
+            0 => Uint::zero(),
+            1 => Uint::MAX,
+            2 => Uint::from(rng.gen::<u64>()),
+            _ => Uint::from_big_endian(&rng.gen::<[u8; 32]>()),
+        }
+    }
+
+    fn uints(rng: &mut StdRng) -> Vec<Uint> {
+        (0..rng.gen_range(0..6)).map(|_| uint(rng)).collect()
+    }
+
+    fn address(rng: &mut StdRng) -> Address {
+        Address::from(rng.gen::<[u8; 20]>())
+    }
+
+    fn word(rng: &mut StdRng) -> [u8; 32] {
+        rng.gen()
+    }
+
+    fn topic(token: Token) -> Vec<u8> {
+        ethabi::encode(&[token])
+    }
+
+    fn uint_array(values: &[Uint]) -> Token {
+        Token::Array(values.iter().map(|value| Token::Uint(*value)).collect())
+    }
+
+    fn strings(values: &[Uint]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn signature(name: &str, params: &[ParamType]) -> Vec<u8> {
+        ethabi::long_signature(name, params).as_bytes().to_vec()
+    }
+
+    fn log(topics: Vec<Vec<u8>>, data: Vec<u8>) -> Log {
+        Log {
+            address: vec![0xaa; 20],
+            topics,
+            data,
+            block_index: 7,
+            ..Default::default()
+        }
+    }
+
+    fn uint_param() -> ParamType {
+        ParamType::Uint(256)
+    }
+
+    fn uints_param() -> ParamType {
+        ParamType::Array(Box::new(ParamType::Uint(256)))
+    }
+
+    fn bytes32_param() -> ParamType {
+        ParamType::FixedBytes(32)
+    }
+
+    fn split_params() -> Vec<ParamType> {
+        vec![ParamType::Address, ParamType::Address, bytes32_param(), bytes32_param(), uints_param(), uint_param()]
+    }
+
+    fn resolution_params() -> Vec<ParamType> {
+        vec![bytes32_param(), ParamType::Address, bytes32_param(), uint_param(), uints_param()]
+    }
+
+    fn redemption_params() -> Vec<ParamType> {
+        vec![ParamType::Address, ParamType::Address, bytes32_param(), bytes32_param(), uints_param(), uint_param()]
+    }
+
+    // A valid log of every decoder, with the check that it decodes, for the negative tests to break
+    type Decodes = fn(&Log) -> bool;
+
+    fn valid_logs(rng: &mut StdRng) -> Vec<(&'static str, Log, Decodes)> {
+        let addr = |rng: &mut StdRng| topic(Token::Address(address(rng)));
+        let num = |rng: &mut StdRng| topic(Token::Uint(uint(rng)));
+        vec![
+            ("TokenRegistered", log(vec![TOKEN_REGISTERED_SIG.to_vec(), num(rng), num(rng), word(rng).to_vec()], Vec::new()),
+                |log| decode_token_registered(log).is_some()),
+            ("FixedProductMarketMakerCreation", log(vec![FPMM_CREATION_SIG.to_vec(), addr(rng), addr(rng), addr(rng)],
+                ethabi::encode(&[Token::Address(address(rng)), Token::Array(vec![Token::FixedBytes(word(rng).to_vec())]), Token::Uint(uint(rng))])),
+                |log| decode_fpmm_creation(log).is_some()),
+            ("OrderFilled", log(vec![ORDER_FILLED_SIG.to_vec(), word(rng).to_vec(), addr(rng), addr(rng)],
+                ethabi::encode(&[Token::Uint(uint(rng)), Token::Uint(uint(rng)), Token::Uint(uint(rng)), Token::Uint(uint(rng)), Token::Uint(uint(rng))])),
+                |log| decode_order_filled(log).is_some()),
+            ("OrdersMatched", log(vec![ORDERS_MATCHED_SIG.to_vec(), word(rng).to_vec(), addr(rng)],
+                ethabi::encode(&[Token::Uint(uint(rng)), Token::Uint(uint(rng)), Token::Uint(uint(rng)), Token::Uint(uint(rng))])),
+                |log| decode_orders_matched(log).is_some()),
+            ("TransferSingle", log(vec![TRANSFER_SINGLE_SIG.to_vec(), addr(rng), addr(rng), addr(rng)],
+                ethabi::encode(&[Token::Uint(uint(rng)), Token::Uint(uint(rng))])),
+                |log| decode_erc1155_transfer_single(log).is_some()),
+            ("TransferBatch", log(vec![TRANSFER_BATCH_SIG.to_vec(), addr(rng), addr(rng), addr(rng)],
+                ethabi::encode(&[uint_array(&[uint(rng)]), uint_array(&[uint(rng)])])),
+                |log| decode_erc1155_transfer_batch(log).is_some()),
+            ("Transfer", log(vec![ERC20_TRANSFER_SIG.to_vec(), addr(rng), addr(rng)], ethabi::encode(&[Token::Uint(uint(rng))])),
+                |log| decode_erc20_transfer(log).is_some()),
+            ("Approval", log(vec![ERC20_APPROVAL_SIG.to_vec(), addr(rng), addr(rng)], ethabi::encode(&[Token::Uint(uint(rng))])),
+                |log| decode_erc20_approval(log).is_some()),
+            ("Claimed", log(vec![CLAIMED_SIG.to_vec(), addr(rng)], ethabi::encode(&[Token::Uint(uint(rng))])),
+                |log| decode_merkle_claimed(log).is_some()),
+            ("ConditionPreparation", log(
+                vec![signature("ConditionPreparation", &[bytes32_param(), ParamType::Address, bytes32_param(), uint_param()]), word(rng).to_vec(), addr(rng), word(rng).to_vec()],
+                ethabi::encode(&[Token::Uint(uint(rng))])),
+                |log| matches!(decode_ctf_events(log), Some(CtfEventType::ConditionPreparation(_)))),
+            ("ConditionResolution", log(
+                vec![signature("ConditionResolution", &resolution_params()), word(rng).to_vec(), addr(rng), word(rng).to_vec()],
+                ethabi::encode(&[Token::Uint(Uint::from(2)), uint_array(&[Uint::one(), Uint::zero()])])),
+                |log| matches!(decode_ctf_events(log), Some(CtfEventType::ConditionResolution(_)))),
+            ("PositionSplit", log(
+                vec![signature("PositionSplit", &split_params()), addr(rng), word(rng).to_vec(), word(rng).to_vec()],
+                ethabi::encode(&[Token::Address(address(rng)), uint_array(&[Uint::one(), Uint::from(2)]), Token::Uint(uint(rng))])),
+                |log| matches!(decode_ctf_events(log), Some(CtfEventType::PositionSplit(_)))),
+            ("PositionsMerge", log(
+                vec![signature("PositionsMerge", &split_params()), addr(rng), word(rng).to_vec(), word(rng).to_vec()],
+                ethabi::encode(&[Token::Address(address(rng)), uint_array(&[Uint::one(), Uint::from(2)]), Token::Uint(uint(rng))])),
+                |log| matches!(decode_ctf_events(log), Some(CtfEventType::PositionMerge(_)))),
+            ("PayoutRedemption", log(
+                vec![signature("PayoutRedemption", &redemption_params()), addr(rng), addr(rng), word(rng).to_vec()],
+                ethabi::encode(&[Token::FixedBytes(word(rng).to_vec()), uint_array(&[Uint::one()]), Token::Uint(uint(rng))])),
+                |log| matches!(decode_ctf_events(log), Some(CtfEventType::PositionRedeem(_)))),
+            ("FeeCharged", log(vec![FEE_CHARGED_SIG.to_vec(), addr(rng)], ethabi::encode(&[Token::Uint(uint(rng)), Token::Uint(uint(rng))])),
+                |log| matches!(decode_exchange_events(log), Some(ExchangeEventType::FeeCharged(_)))),
+            ("FPMMBuy", log(vec![FPMM_BUY_SIG.to_vec(), addr(rng), topic(Token::Uint(Uint::one()))],
+                ethabi::encode(&[Token::Uint(uint(rng)), Token::Uint(uint(rng)), Token::Uint(uint(rng))])),
+                |log| decode_fpmm_trade(log).is_some()),
+            ("QuestionInitialized", log(vec![QUESTION_INITIALIZED_SIG.to_vec(), word(rng).to_vec()],
+                ethabi::encode(&[
+                    Token::Uint(uint(rng)), Token::Address(address(rng)), Token::Bytes(b"q: Will it rain?".to_vec()),
+                    Token::String("Will it rain?".to_string()), Token::Address(address(rng)), Token::Uint(uint(rng)), Token::Uint(uint(rng)),
+                ])),
+                |log| decode_question_initialized(log).is_some()),
+        ]
+    }
+
+    #[test]
+    fn signatures_match_event_definitions() {
+        let (uint, address, bytes32) = (uint_param(), ParamType::Address, bytes32_param());
+        let roles = [address.clone(), address.clone()];
+        let fpmm_trade = [address.clone(), uint.clone(), uint.clone(), uint.clone(), uint.clone()];
+        let cases: Vec<([u8; 32], &str, Vec<ParamType>)> = vec![
+            (TOKEN_REGISTERED_SIG, "TokenRegistered", vec![uint.clone(), uint.clone(), bytes32.clone()]),
+            (ORDER_FILLED_SIG, "OrderFilled", vec![bytes32.clone(), address.clone(), address.clone(), uint.clone(), uint.clone(), uint.clone(), uint.clone(), uint.clone()]),
+            (ORDERS_MATCHED_SIG, "OrdersMatched", vec![bytes32.clone(), address.clone(), uint.clone(), uint.clone(), uint.clone(), uint.clone()]),
+            (ORDER_CANCELLED_SIG, "OrderCancelled", vec![bytes32.clone()]),
+            (FEE_CHARGED_SIG, "FeeCharged", vec![address.clone(), uint.clone(), uint.clone()]),
+            (TRADING_PAUSED_SIG, "TradingPaused", vec![address.clone()]),
+            (TRADING_UNPAUSED_SIG, "TradingUnpaused", vec![address.clone()]),
+            (NEW_ADMIN_SIG, "NewAdmin", roles.to_vec()),
+            (NEW_OPERATOR_SIG, "NewOperator", roles.to_vec()),
+            (REMOVED_ADMIN_SIG, "RemovedAdmin", roles.to_vec()),
+            (REMOVED_OPERATOR_SIG, "RemovedOperator", roles.to_vec()),
+            (PROXY_FACTORY_UPDATED_SIG, "ProxyFactoryUpdated", roles.to_vec()),
+            (SAFE_FACTORY_UPDATED_SIG, "SafeFactoryUpdated", roles.to_vec()),
+            (FPMM_CREATION_SIG, "FixedProductMarketMakerCreation",
+                vec![address.clone(), address.clone(), address.clone(), address.clone(), ParamType::Array(Box::new(bytes32.clone())), uint.clone()]),
+            (FPMM_BUY_SIG, "FPMMBuy", fpmm_trade.to_vec()),
+            (FPMM_SELL_SIG, "FPMMSell", fpmm_trade.to_vec()),
+            (ERC20_TRANSFER_SIG, "Transfer", vec![address.clone(), address.clone(), uint.clone()]),
+            (ERC20_APPROVAL_SIG, "Approval", vec![address.clone(), address.clone(), uint.clone()]),
+            (CLAIMED_SIG, "Claimed", vec![address.clone(), uint.clone()]),
+            (TRANSFER_SINGLE_SIG, "TransferSingle", vec![address.clone(), address.clone(), address.clone(), uint.clone(), uint.clone()]),
+            (TRANSFER_BATCH_SIG, "TransferBatch", vec![address.clone(), address.clone(), address.clone(), uints_param(), uints_param()]),
+            (CONDITION_PREPARATION_SIG, "ConditionPreparation", vec![bytes32.clone(), address.clone(), bytes32.clone(), uint.clone()]),
+            (CONDITION_RESOLUTION_SIG, "ConditionResolution", resolution_params()),
+            (POSITION_SPLIT_SIG, "PositionSplit", split_params()),
+            (POSITIONS_MERGE_SIG, "PositionsMerge", split_params()),
+            (PAYOUT_REDEMPTION_SIG, "PayoutRedemption", redemption_params()),
+        ];
+
+        for (sig, name, params) in cases {
+            assert_eq!(sig.to_vec(), signature(name, &params), "{} topic0", name);
+        }
+    }
+
+    #[test]
+    fn token_registered_round_trip() {
+        let mut rng = rng();
+        for _ in 0..CASES {
+            let (token0, token1, condition_id) = (uint(&mut rng), uint(&mut rng), word(&mut rng));
+            let log = Log {
+                block_index: rng.gen(),
+                ..log(vec![TOKEN_REGISTERED_SIG.to_vec(), topic(Token::Uint(token0)), topic(Token::Uint(token1)), condition_id.to_vec()], Vec::new())
+            };
+
+            let decoded = decode_token_registered(&log).unwrap();
+            assert_eq!(decoded.token0, token0.to_string());
+            assert_eq!(decoded.token1, token1.to_string());
+            assert_eq!(decoded.condition_id, condition_id.to_vec());
+            assert_eq!(decoded.evt_index, log.block_index);
+        }
+    }
+
+    #[test]
+    fn fpmm_creation_round_trip() {
+        let mut rng = rng();
+        for _ in 0..CASES {
+            let (creator, conditional_tokens, collateral, market) = (address(&mut rng), address(&mut rng), address(&mut rng), address(&mut rng));
+            let condition_ids: Vec<Vec<u8>> = (0..rng.gen_range(0..4)).map(|_| word(&mut rng).to_vec()).collect();
+            let fee = uint(&mut rng);
+            let data = ethabi::encode(&[
+                Token::Address(market),
+                Token::Array(condition_ids.iter().cloned().map(Token::FixedBytes).collect()),
+                Token::Uint(fee),
+            ]);
+            let log = log(vec![FPMM_CREATION_SIG.to_vec(), topic(Token::Address(creator)), topic(Token::Address(conditional_tokens)), topic(Token::Address(collateral))], data);
+
+            let decoded = decode_fpmm_creation(&log).unwrap();
+            assert_eq!(decoded.creator, creator.as_bytes());
+            assert_eq!(decoded.collateral_token, collateral.as_bytes());
+            assert_eq!(decoded.fixed_product_market_maker, market.as_bytes());
+            assert_eq!(decoded.condition_ids, condition_ids);
+            assert_eq!(decoded.fee, fee.to_string());
+        }
+    }
+
+    #[test]
+    fn order_filled_round_trip() {
+        let mut rng = rng();
+        for _ in 0..CASES {
+            let (order_hash, maker, taker) = (word(&mut rng), address(&mut rng), address(&mut rng));
+            let amounts: Vec<Uint> = (0..5).map(|_| uint(&mut rng)).collect();
+            let data = ethabi::encode(&amounts.iter().map(|amount| Token::Uint(*amount)).collect::<Vec<_>>());
+            let log = log(vec![ORDER_FILLED_SIG.to_vec(), order_hash.to_vec(), topic(Token::Address(maker)), topic(Token::Address(taker))], data);
+
+            let decoded = decode_order_filled(&log).unwrap();
+            assert_eq!(decoded.order_hash, order_hash.to_vec());
+            assert_eq!(decoded.maker, maker.as_bytes());
+            assert_eq!(decoded.taker, taker.as_bytes());
+            assert_eq!(
+                [decoded.maker_asset_id, decoded.taker_asset_id, decoded.maker_amount_filled, decoded.taker_amount_filled, decoded.fee].to_vec(),
+                strings(&amounts),
+            );
+        }
+    }
+
+    #[test]
+    fn orders_matched_round_trip() {
+        let mut rng = rng();
+        for _ in 0..CASES {
+            let (order_hash, maker) = (word(&mut rng), address(&mut rng));
+            let amounts: Vec<Uint> = (0..4).map(|_| uint(&mut rng)).collect();
+            let data = ethabi::encode(&amounts.iter().map(|amount| Token::Uint(*amount)).collect::<Vec<_>>());
+            let log = log(vec![ORDERS_MATCHED_SIG.to_vec(), order_hash.to_vec(), topic(Token::Address(maker))], data);
+
+            let decoded = decode_orders_matched(&log).unwrap();
+            assert_eq!(decoded.taker_order_hash, order_hash.to_vec());
+            assert_eq!(decoded.taker_order_maker, maker.as_bytes());
+            assert_eq!(
+                [decoded.maker_asset_id, decoded.taker_asset_id, decoded.maker_amount_filled, decoded.taker_amount_filled].to_vec(),
+                strings(&amounts),
+            );
+        }
+    }
+
+    #[test]
+    fn erc1155_transfers_round_trip() {
+        let mut rng = rng();
+        for _ in 0..CASES {
+            let (operator, from, to) = (address(&mut rng), address(&mut rng), address(&mut rng));
+            let topics = vec![topic(Token::Address(operator)), topic(Token::Address(from)), topic(Token::Address(to))];
+
+            let (id, value) = (uint(&mut rng), uint(&mut rng));
+            let single = log([vec![TRANSFER_SINGLE_SIG.to_vec()], topics.clone()].concat(), ethabi::encode(&[Token::Uint(id), Token::Uint(value)]));
+            let decoded = decode_erc1155_transfer_single(&single).unwrap();
+            assert_eq!((decoded.operator, decoded.from, decoded.to), (operator.as_bytes().to_vec(), from.as_bytes().to_vec(), to.as_bytes().to_vec()));
+            assert_eq!((decoded.id, decoded.value), (id.to_string(), value.to_string()));
+            assert_eq!(decoded.contract_address, single.address);
+
+            let ids = uints(&mut rng);
+            let values: Vec<Uint> = ids.iter().map(|_| uint(&mut rng)).collect();
+            let batch = log([vec![TRANSFER_BATCH_SIG.to_vec()], topics].concat(), ethabi::encode(&[uint_array(&ids), uint_array(&values)]));
+            let decoded = decode_erc1155_transfer_batch(&batch).unwrap();
+            assert_eq!((decoded.operator, decoded.from, decoded.to), (operator.as_bytes().to_vec(), from.as_bytes().to_vec(), to.as_bytes().to_vec()));
+            assert_eq!((decoded.ids, decoded.values), (strings(&ids), strings(&values)));
+        }
+    }
+
+    #[test]
+    fn erc20_events_round_trip() {
+        let mut rng = rng();
+        for _ in 0..CASES {
+            let (from, to, value) = (address(&mut rng), address(&mut rng), uint(&mut rng));
+            let topics = vec![topic(Token::Address(from)), topic(Token::Address(to))];
+            let data = ethabi::encode(&[Token::Uint(value)]);
+
+            let transfer = log([vec![ERC20_TRANSFER_SIG.to_vec()], topics.clone()].concat(), data.clone());
+            let decoded = decode_erc20_transfer(&transfer).unwrap();
+            assert_eq!((decoded.from, decoded.to, decoded.value), (from.as_bytes().to_vec(), to.as_bytes().to_vec(), value.to_string()));
+
+            let approval = log([vec![ERC20_APPROVAL_SIG.to_vec()], topics].concat(), data);
+            let decoded = decode_erc20_approval(&approval).unwrap();
+            assert_eq!((decoded.owner, decoded.spender, decoded.value), (from.as_bytes().to_vec(), to.as_bytes().to_vec(), value.to_string()));
+        }
+    }
+
+    #[test]
+    fn merkle_claimed_round_trip() {
+        let mut rng = rng();
+        for _ in 0..CASES {
+            let (recipient, amount) = (address(&mut rng), uint(&mut rng));
+
+            let claimed = log(vec![CLAIMED_SIG.to_vec(), topic(Token::Address(recipient))], ethabi::encode(&[Token::Uint(amount)]));
+            let decoded = decode_merkle_claimed(&claimed).unwrap();
+            assert_eq!((decoded.airdrop_recipient, decoded.amount), (recipient.as_bytes().to_vec(), amount.to_string()));
+            assert_eq!(decoded.token_address, Hex(&claimed.address).to_string());
+
+            // Recipient in data instead of indexed, or one more indexed value, is another event
+            let unindexed = log(vec![CLAIMED_SIG.to_vec()], ethabi::encode(&[Token::Address(recipient), Token::Uint(amount)]));
+            assert!(decode_merkle_claimed(&unindexed).is_none());
+            let extended = Log { topics: [claimed.topics.clone(), vec![word(&mut rng).to_vec()]].concat(), ..claimed };
+            assert!(decode_merkle_claimed(&extended).is_none());
+        }
+    }
+
+    #[test]
+    fn ctf_events_round_trip() {
+        let mut rng = rng();
+        for _ in 0..CASES {
+            let (condition_id, question_id, parent_collection_id) = (word(&mut rng), word(&mut rng), word(&mut rng));
+            let (oracle, stakeholder, collateral) = (address(&mut rng), address(&mut rng), address(&mut rng));
+            let (outcome_slot_count, amount, index_sets) = (rng.gen_range(2..8u32), uint(&mut rng), uints(&mut rng));
+
+            let preparation = log(
+                vec![signature("ConditionPreparation", &[bytes32_param(), ParamType::Address, bytes32_param(), uint_param()]),
+                    condition_id.to_vec(), topic(Token::Address(oracle)), question_id.to_vec()],
+                ethabi::encode(&[Token::Uint(outcome_slot_count.into())]));
+            let Some(CtfEventType::ConditionPreparation(decoded)) = decode_ctf_events(&preparation) else { panic!("ConditionPreparation") };
+            assert_eq!((decoded.condition_id, decoded.question_id), (condition_id.to_vec(), question_id.to_vec()));
+            assert_eq!((decoded.oracle, decoded.outcome_slot_count), (oracle.as_bytes().to_vec(), outcome_slot_count));
+
+            let payouts: Vec<Uint> = (0..outcome_slot_count).map(|_| Uint::from(rng.gen::<u64>())).collect();
+            let resolution = log(
+                vec![signature("ConditionResolution", &resolution_params()), condition_id.to_vec(), topic(Token::Address(oracle)), question_id.to_vec()],
+                ethabi::encode(&[Token::Uint(outcome_slot_count.into()), uint_array(&payouts)]));
+            let Some(CtfEventType::ConditionResolution(decoded)) = decode_ctf_events(&resolution) else { panic!("ConditionResolution") };
+            let denominator = payouts.iter().fold(BigInt::zero(), |sum, payout| sum + BigInt::from_unsigned_bytes_be(&topic(Token::Uint(*payout))));
+            assert_eq!((decoded.payout_numerators, decoded.payout_denominator), (strings(&payouts), denominator.to_string()));
+
+            for (name, merge) in [("PositionSplit", false), ("PositionsMerge", true)] {
+                let log = log(
+                    vec![signature(name, &split_params()), topic(Token::Address(stakeholder)), parent_collection_id.to_vec(), condition_id.to_vec()],
+                    ethabi::encode(&[Token::Address(collateral), uint_array(&index_sets), Token::Uint(amount)]));
+                let (stakeholder_, collateral_, parent_, condition_, partition, amount_) = match (decode_ctf_events(&log), merge) {
+                    (Some(CtfEventType::PositionSplit(e)), false) => (e.stakeholder, e.collateral_token, e.parent_collection_id, e.condition_id, e.partition, e.amount),
+                    (Some(CtfEventType::PositionMerge(e)), true) => (e.stakeholder, e.collateral_token, e.parent_collection_id, e.condition_id, e.partition, e.amount),
+                    _ => panic!("{}", name),
+                };
+                assert_eq!((stakeholder_, collateral_), (stakeholder.as_bytes().to_vec(), collateral.as_bytes().to_vec()));
+                assert_eq!((parent_, condition_), (parent_collection_id.to_vec(), condition_id.to_vec()));
+                assert_eq!((partition, amount_), (strings(&index_sets), amount.to_string()));
+            }
+
+            let redemption = log(
+                vec![signature("PayoutRedemption", &redemption_params()), topic(Token::Address(stakeholder)), topic(Token::Address(collateral)), parent_collection_id.to_vec()],
+                ethabi::encode(&[Token::FixedBytes(condition_id.to_vec()), uint_array(&index_sets), Token::Uint(amount)]));
+            let Some(CtfEventType::PositionRedeem(decoded)) = decode_ctf_events(&redemption) else { panic!("PayoutRedemption") };
+            assert_eq!((decoded.redeemer, decoded.collateral_token), (stakeholder.as_bytes().to_vec(), collateral.as_bytes().to_vec()));
+            assert_eq!((decoded.parent_collection_id, decoded.condition_id), (parent_collection_id.to_vec(), condition_id.to_vec()));
+            assert_eq!((decoded.index_sets, decoded.payout), (strings(&index_sets), amount.to_string()));
+        }
+    }
+
+    #[test]
+    fn exchange_events_round_trip() {
+        let mut rng = rng();
+        for _ in 0..CASES {
+            let (first, second, order_hash) = (address(&mut rng), address(&mut rng), word(&mut rng));
+            let (first_topic, second_topic) = (topic(Token::Address(first)), topic(Token::Address(second)));
+
+            let cancelled = log(vec![ORDER_CANCELLED_SIG.to_vec(), order_hash.to_vec()], Vec::new());
+            let Some(ExchangeEventType::OrderCancelled(decoded)) = decode_exchange_events(&cancelled) else { panic!("OrderCancelled") };
+            assert_eq!((decoded.order_hash, decoded.exchange), (order_hash.to_vec(), cancelled.address.clone()));
+
+            let (token_id, amount) = (uint(&mut rng), uint(&mut rng));
+            let fee = log(vec![FEE_CHARGED_SIG.to_vec(), first_topic.clone()], ethabi::encode(&[Token::Uint(token_id), Token::Uint(amount)]));
+            let Some(ExchangeEventType::FeeCharged(decoded)) = decode_exchange_events(&fee) else { panic!("FeeCharged") };
+            assert_eq!((decoded.receiver, decoded.token_id, decoded.amount), (first.as_bytes().to_vec(), token_id.to_string(), amount.to_string()));
+
+            for (sig, paused) in [(TRADING_PAUSED_SIG, true), (TRADING_UNPAUSED_SIG, false)] {
+                let log = log(vec![sig.to_vec(), first_topic.clone()], Vec::new());
+                let Some(ExchangeEventType::TradingPaused(decoded)) = decode_exchange_events(&log) else { panic!("TradingPaused") };
+                assert_eq!((decoded.pauser, decoded.paused), (first.as_bytes().to_vec(), paused));
+            }
+
+            for (sig, role, granted) in [
+                (NEW_ADMIN_SIG, "ADMIN", true), (REMOVED_ADMIN_SIG, "ADMIN", false),
+                (NEW_OPERATOR_SIG, "OPERATOR", true), (REMOVED_OPERATOR_SIG, "OPERATOR", false),
+            ] {
+                let log = log(vec![sig.to_vec(), first_topic.clone(), second_topic.clone()], Vec::new());
+                let Some(ExchangeEventType::RoleChange(decoded)) = decode_exchange_events(&log) else { panic!("{}", role) };
+                assert_eq!((decoded.role.as_str(), decoded.granted), (role, granted));
+                assert_eq!((decoded.account, decoded.admin), (first.as_bytes().to_vec(), second.as_bytes().to_vec()));
+            }
+
+            for (sig, factory) in [(PROXY_FACTORY_UPDATED_SIG, "PROXY"), (SAFE_FACTORY_UPDATED_SIG, "SAFE")] {
+                let log = log(vec![sig.to_vec(), first_topic.clone(), second_topic.clone()], Vec::new());
+                let Some(ExchangeEventType::FactoryUpdate(decoded)) = decode_exchange_events(&log) else { panic!("{}", factory) };
+                assert_eq!(decoded.factory, factory);
+                assert_eq!((decoded.old_factory, decoded.new_factory), (first.as_bytes().to_vec(), second.as_bytes().to_vec()));
+            }
+        }
+    }
+
+    #[test]
+    fn fpmm_trade_round_trip() {
+        let mut rng = rng();
+        for _ in 0..CASES {
+            let (trader, outcome_index) = (address(&mut rng), rng.gen::<u32>());
+            let amounts: Vec<Uint> = (0..3).map(|_| uint(&mut rng)).collect();
+            for (sig, buy) in [(FPMM_BUY_SIG, true), (FPMM_SELL_SIG, false)] {
+                let log = log(
+                    vec![sig.to_vec(), topic(Token::Address(trader)), topic(Token::Uint(outcome_index.into()))],
+                    ethabi::encode(&amounts.iter().map(|amount| Token::Uint(*amount)).collect::<Vec<_>>()));
+
+                let decoded = decode_fpmm_trade(&log).unwrap();
+                assert_eq!(matches!(decoded.side, FpmmTradeSide::Buy), buy);
+                assert_eq!((decoded.trader, decoded.outcome_index), (trader.as_bytes().to_vec(), outcome_index));
+                assert_eq!(
+                    [decoded.collateral_amount, decoded.fee_amount, decoded.outcome_tokens].map(|amount| amount.to_string()).to_vec(),
+                    strings(&amounts),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn question_initialized_round_trip() {
+        let mut rng = rng();
+        for _ in 0..CASES {
+            let (question_id, creator, reward_token) = (word(&mut rng), address(&mut rng), address(&mut rng));
+            let (request_timestamp, reward, proposal_bond) = (uint(&mut rng), uint(&mut rng), uint(&mut rng));
+            let ancillary_data: Vec<u8> = (0..rng.gen_range(0..100)).map(|_| rng.gen()).collect();
+            let question: String = (0..rng.gen_range(0..100)).map(|_| rng.gen_range('a'..='z')).collect();
+            let data = ethabi::encode(&[
+                Token::Uint(request_timestamp),
+                Token::Address(creator),
+                Token::Bytes(ancillary_data.clone()),
+                Token::String(question.clone()),
+                Token::Address(reward_token),
+                Token::Uint(reward),
+                Token::Uint(proposal_bond),
+            ]);
+            let log = log(vec![QUESTION_INITIALIZED_SIG.to_vec(), question_id.to_vec()], data);
+
+            let decoded = decode_question_initialized(&log).unwrap();
+            assert_eq!(decoded.question_id, format!("0x{}", Hex(&question_id)));
+            assert_eq!(decoded.request_timestamp, request_timestamp.to_string());
+            assert_eq!(decoded.creator, format!("0x{}", Hex(creator.as_bytes())));
+            assert_eq!(decoded.ancillary_data, format!("0x{}", Hex(&ancillary_data)));
+            assert_eq!(decoded.question, question);
+            assert_eq!(decoded.reward_token, format!("0x{}", Hex(reward_token.as_bytes())));
+            assert_eq!((decoded.reward, decoded.proposal_bond), (reward.to_string(), proposal_bond.to_string()));
+        }
+    }
+
+    #[test]
+    fn valid_logs_decode() {
+        for (name, log, decodes) in valid_logs(&mut rng()) {
+            assert!(decodes(&log), "{} does not decode", name);
+        }
+    }
+
+    #[test]
+    fn event_type_names_what_route_decodes() {
+        let network = &crate::network::POLYGON;
+        for (name, log, _) in valid_logs(&mut rng()) {
+            let address = match name {
+                "TokenRegistered" | "OrderFilled" | "OrdersMatched" | "FeeCharged" => network.ctf_exchange,
+                "FixedProductMarketMakerCreation" => network.fpmm_factory,
+                "Claimed" => network.usdc_merkle_distributor,
+                "Approval" => network.usdc,
+                "ConditionPreparation" | "ConditionResolution" | "PositionSplit" | "PositionsMerge" | "PayoutRedemption" => network.ctf,
+                _ => [0xaa; 20],
+            };
+            let log = Log { address: address.to_vec(), ..log };
+            assert_eq!(event_type(network, &log), Some(name));
+            assert!(route(network, &log).is_some(), "{} is not routed", name);
+        }
+
+        // Unknown topic0s at watched contracts, and CTF events anywhere else
+        let unknown = log(vec![[0x11; 32].to_vec()], Vec::new());
+        for address in [network.ctf_exchange, network.fpmm_factory, network.ctf, network.usdc, network.usdc_merkle_distributor] {
+            assert_eq!(event_type(network, &Log { address: address.to_vec(), ..unknown.clone() }), None);
+        }
+        assert_eq!(event_type(network, &log(vec![POSITION_SPLIT_SIG.to_vec()], Vec::new())), None);
+
+        // Only Claimed is decoded at a merkle distributor, whatever the data
+        let other = Log { address: network.usdc_merkle_distributor.to_vec(), ..log(vec![[0x11; 32].to_vec(), [0; 32].to_vec()], vec![0; 32]) };
+        assert!(route(network, &other).is_none());
+    }
+
+    #[test]
+    fn erc721_transfers_are_not_erc20() {
+        let network = &crate::network::POLYGON;
+        let topic = |byte: u8| vec![byte; 32];
+        let erc721 = Log { address: vec![0xaa; 20], ..log(vec![ERC20_TRANSFER_SIG.to_vec(), topic(0x11), topic(0x22), topic(0x33)], Vec::new()) };
+        assert_eq!(event_type(network, &erc721), None);
+        assert!(route(network, &erc721).is_none());
+        // Not even when it carries data
+        assert!(route(network, &Log { data: vec![0; 32], ..erc721.clone() }).is_none());
+
+        // An ERC20-shaped Transfer without its value is still a failed Transfer
+        let truncated = Log { topics: erc721.topics[..3].to_vec(), ..erc721.clone() };
+        assert_eq!(event_type(network, &truncated), Some("Transfer"));
+        assert!(route(network, &truncated).is_none());
+    }
+
+    #[test]
+    fn collaterals_are_not_merkle_distributors() {
+        for network in crate::network::NETWORKS {
+            for collateral in network.collaterals {
+                assert!(!network.is_merkle_distributor(&collateral.address), "{} {} is a merkle distributor", network.name, collateral.symbol);
+            }
+        }
+
+        // A Claimed-shaped log from WrappedCollateral is not a reward claim
+        let network = &crate::network::POLYGON;
+        let wrapped_collateral = network.collaterals.iter().find(|collateral| collateral.symbol == "WCOL").unwrap();
+        let claimed = Log { address: wrapped_collateral.address.to_vec(), ..log(vec![CLAIMED_SIG.to_vec(), [0; 32].to_vec()], vec![0; 32]) };
+        assert!(!matches!(route(network, &claimed), Some(DecodedEvent::MerkleClaimed(_))));
+        assert_eq!(event_type(network, &claimed), None);
+    }
+
+    #[test]
+    fn truncated_data_returns_none() {
+        let mut rng = rng();
+        for _ in 0..CASES {
+            for (name, log, decodes) in valid_logs(&mut rng) {
+                if log.data.is_empty() {
+                    continue;
+                }
+                // At least a word short: dynamic values may legally lose their trailing padding
+                for keep in [0, log.data.len() - 32, rng.gen_range(0..=log.data.len() - 32)] {
+                    let truncated = Log { data: log.data[..keep].to_vec(), ..log.clone() };
+                    assert!(!decodes(&truncated), "{} decodes from {} of {} bytes", name, keep, log.data.len());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn missing_topics_return_none() {
+        for (name, log, decodes) in valid_logs(&mut rng()) {
+            for count in 0..log.topics.len() {
+                let truncated = Log { topics: log.topics[..count].to_vec(), ..log.clone() };
+                assert!(!decodes(&truncated), "{} decodes from {} of {} topics", name, count, log.topics.len());
+            }
+        }
+    }
+
+    #[test]
+    fn extra_topics_on_ctf_events_return_none() {
+        // The generated bindings match the exact topic count, a CTF event with one more indexed value is another event
+        let ctf_events = ["ConditionPreparation", "ConditionResolution", "PositionSplit", "PositionsMerge", "PayoutRedemption"];
+        for (name, log, decodes) in valid_logs(&mut rng()).into_iter().filter(|(name, _, _)| ctf_events.contains(name)) {
+            let extended = Log { topics: [log.topics.clone(), vec![vec![0u8; 32]]].concat(), ..log };
+            assert!(!decodes(&extended), "{} decodes with {} topics", name, extended.topics.len());
+        }
+    }
+
+    #[test]
+    fn oversized_array_lengths_return_none() {
+        // (event, head word holding the offset of each dynamic value)
+        let dynamic = [
+            ("FixedProductMarketMakerCreation", vec![1]),
+            ("TransferBatch", vec![0, 1]),
+            ("ConditionResolution", vec![1]),
+            ("PositionSplit", vec![1]),
+            ("PositionsMerge", vec![1]),
+            ("PayoutRedemption", vec![1]),
+            ("QuestionInitialized", vec![2, 3]),
+        ];
+        let lengths = [Uint::from(u32::MAX), Uint::from(u64::MAX), Uint::MAX, Uint::from(1u64 << 40)];
+
+        let logs = valid_logs(&mut rng());
+        for (event, heads) in dynamic {
+            let (_, log, decodes) = logs.iter().find(|(name, _, _)| *name == event).unwrap();
+            for head in heads {
+                let offset = Uint::from_big_endian(&log.data[head * 32..head * 32 + 32]).as_usize();
+                for length in lengths.iter().chain([Uint::from(log.data.len())].iter()) {
+                    let mut data = log.data.clone();
+                    data[offset..offset + 32].copy_from_slice(&topic(Token::Uint(*length)));
+                    let oversized = Log { data, ..log.clone() };
+                    assert!(!decodes(&oversized), "{} decodes with length {} at head {}", event, length, head);
+                }
+            }
+        }
+    }
+}
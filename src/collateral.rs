@@ -0,0 +1,67 @@
+// Collateral registry: ERC20 metadata of the tokens backing CTF positions and FPMM markets
+use crate::network::NetworkProfile;
+use crate::pb::contract::v1 as contract;
+use std::cmp::Ordering;
+use substreams::scalar::BigInt;
+use substreams::store::StoreGet;
+use substreams::Hex;
+
+// Wallet cash and market volume are kept in USDC base units
+pub const USDC_DECIMALS: u32 = 6;
+
+pub fn collateral_key(address: &[u8]) -> String {
+    format!("collateral:0x{}", Hex(address))
+}
+
+// Registered collaterals missing from the network profile: their decimals are not known and their amounts are left unvalued
+pub const UNKNOWN_SOURCE: &str = "unknown";
+
+// Metadata of a collateral first seen at block_number: the profile entry, else flagged as unknown with no decimals
+pub fn resolve(network: &NetworkProfile, address: &[u8], block_number: u64) -> contract::CollateralToken {
+    let (symbol, decimals, source) = match network.known_collateral(address) {
+        Some(known) => (known.symbol.to_string(), known.decimals, "network"),
+        None => (String::new(), 0, UNKNOWN_SOURCE),
+    };
+
+    contract::CollateralToken {
+        address: address.to_vec(),
+        symbol,
+        decimals,
+        first_seen_block: block_number,
+        source: source.to_string(),
+    }
+}
+
+// store_position_collaterals key of an outcome token, the decimal token id as in store_positions
+pub fn position_key(token_id: &str) -> String {
+    format!("position:{}", token_id)
+}
+
+// Decimals of a registered collateral, USDC's when it has not been seen (its first split predates the start block),
+// None when it is an unknown collateral
+pub fn decimals(collaterals: &impl StoreGet<contract::CollateralToken>, address: &[u8]) -> Option<u32> {
+    known_decimals(collaterals.get_last(collateral_key(address)))
+}
+
+// Decimals of an outcome token: its collateral's, USDC's when its split has not been seen, None when its collateral
+// is unknown
+pub fn position_decimals(positions: &impl StoreGet<contract::CollateralToken>, token_id: &str) -> Option<u32> {
+    known_decimals(positions.get_last(position_key(token_id)))
+}
+
+fn known_decimals(collateral: Option<contract::CollateralToken>) -> Option<u32> {
+    match collateral {
+        Some(collateral) if collateral.source == UNKNOWN_SOURCE => None,
+        Some(collateral) => Some(collateral.decimals),
+        None => Some(USDC_DECIMALS),
+    }
+}
+
+// Rescale a raw collateral amount to USDC base units, dropping digits beyond the 6th decimal
+pub fn to_usdc_units(amount: BigInt, decimals: u32) -> BigInt {
+    match decimals.cmp(&USDC_DECIMALS) {
+        Ordering::Equal => amount,
+        Ordering::Greater => amount / BigInt::from(10u64).pow(decimals - USDC_DECIMALS),
+        Ordering::Less => amount * BigInt::from(10u64).pow(USDC_DECIMALS - decimals),
+    }
+}
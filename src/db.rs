@@ -0,0 +1,206 @@
+// DatabaseChanges rows for substreams-sink-sql, see schema.sql and schema.clickhouse.sql
+use crate::collateral;
+use crate::pb::contract::v1 as contract;
+use substreams::pb::substreams::Clock;
+use substreams::scalar::BigInt;
+use substreams::store::{DeltaBigInt, DeltaProto, Deltas, StoreGetProto};
+use substreams::Hex;
+use substreams_database_change::tables::Tables;
+
+// Markets touched in the block, keyed by condition id
+pub fn market_rows(tables: &mut Tables, market_pnls: &contract::OnChainMarketPnLs) {
+    for market in &market_pnls.market_pnls {
+        let row = tables.upsert_row("markets", &market.condition_id);
+        row.set("question_id", &market.question_id)
+            .set("total_volume", &market.total_volume)
+            .set("total_trades", &market.total_trades)
+            .set("total_fees", &market.total_fees)
+            .set("is_neg_risk", market.is_neg_risk)
+            .set("block_number", market.block_number);
+        // Resolution columns stay NULL until the condition resolves
+        if !market.resolution_price.is_empty() {
+            row.set("winning_outcome", &market.winning_outcome)
+                .set("resolution_price", &market.resolution_price);
+        }
+        if let Some(created_at) = &market.created_at {
+            row.set("created_at", created_at);
+        }
+        if let Some(resolved_at) = &market.resolved_at {
+            row.set("resolved_at", resolved_at);
+        }
+    }
+}
+
+// One row per enriched fill, keyed by tx hash + log index
+pub fn trade_rows(tables: &mut Tables, trades: &contract::Trades) {
+    for trade in &trades.trades {
+        let row = tables.create_row("trades", [
+            ("evt_tx_hash", trade.evt_tx_hash.clone()),
+            ("evt_index", trade.evt_index.to_string()),
+        ]);
+        row.set("evt_block_number", trade.evt_block_number)
+            .set("venue", &trade.venue)
+            .set("exchange_address", &trade.exchange_address)
+            .set("order_hash", &trade.order_hash)
+            .set("maker_address", &trade.maker_address)
+            .set("taker_address", &trade.taker_address)
+            .set("maker_side", &trade.maker_side)
+            .set("taker_side", &trade.taker_side)
+            .set("token_id", &trade.token_id)
+            .set("outcome_index", trade.outcome_index)
+            .set("condition_id", &trade.condition_id)
+            .set("question_id", &trade.question_id)
+            .set("price", &trade.price)
+            .set("shares", &trade.shares)
+            .set("usdc_amount", &trade.usdc_amount)
+            .set("fee_usdc", &trade.fee_usdc)
+            .set("is_taker_order", trade.is_taker_order);
+        if let Some(block_time) = &trade.evt_block_time {
+            row.set("evt_block_time", block_time);
+        }
+    }
+}
+
+// Latest traded price per outcome token, FPMM trades carry no token id and are skipped
+pub fn token_price_rows(tables: &mut Tables, trades: &contract::Trades) {
+    for trade in trades.trades.iter().filter(|trade| !trade.token_id.is_empty()) {
+        tables.upsert_row("token_prices", &trade.token_id)
+            .set("price", &trade.price)
+            .set("block_number", trade.evt_block_number);
+    }
+}
+
+// CTF ERC1155 transfers keyed by tx hash + log index + position within the batch (0 for singles),
+// amounts in the decimals of each token's collateral
+pub fn transfer_rows(tables: &mut Tables, ctf_events: &contract::CtfEvents, position_collaterals: &StoreGetProto<contract::CollateralToken>) {
+    for transfer in &ctf_events.transfer_singles {
+        transfer_row(
+            tables,
+            &transfer.evt_tx_hash,
+            transfer.evt_index,
+            0,
+            transfer.evt_block_number,
+            transfer.evt_block_time.as_ref(),
+            (&transfer.operator, &transfer.from, &transfer.to),
+            &transfer.token_id,
+            &transfer.value,
+            position_collaterals,
+        );
+    }
+
+    for batch in &ctf_events.transfer_batches {
+        for (batch_index, (token_id, value)) in batch.token_ids.iter().zip(&batch.values).enumerate() {
+            transfer_row(
+                tables,
+                &batch.evt_tx_hash,
+                batch.evt_index,
+                batch_index,
+                batch.evt_block_number,
+                batch.evt_block_time.as_ref(),
+                (&batch.operator, &batch.from, &batch.to),
+                token_id,
+                value,
+                position_collaterals,
+            );
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn transfer_row(
+    tables: &mut Tables,
+    evt_tx_hash: &str,
+    evt_index: u32,
+    batch_index: usize,
+    block_number: u64,
+    block_time: Option<&prost_types::Timestamp>,
+    (operator, from, to): (&[u8], &[u8], &[u8]),
+    token_id: &str,
+    value: &str,
+    position_collaterals: &StoreGetProto<contract::CollateralToken>,
+) {
+    let row = tables.create_row("transfers", [
+        ("evt_tx_hash", evt_tx_hash.to_string()),
+        ("evt_index", evt_index.to_string()),
+        ("batch_index", batch_index.to_string()),
+    ]);
+    row.set("evt_block_number", block_number)
+        .set("operator", format!("0x{}", Hex(operator)))
+        .set("from_address", format!("0x{}", Hex(from)))
+        .set("to_address", format!("0x{}", Hex(to)))
+        .set("token_id", token_id);
+    // amount stays NULL for a token of an unknown collateral
+    if let Some(decimals) = collateral::position_decimals(position_collaterals, token_id) {
+        row.set("amount", value.parse::<BigInt>().unwrap_or_default().to_decimal(decimals as u64));
+    }
+    if let Some(block_time) = block_time {
+        row.set("evt_block_time", block_time);
+    }
+}
+
+// Merkle distributor claims keyed by tx hash + log index
+pub fn reward_claim_rows(tables: &mut Tables, claims: &contract::MerkleDistributorEvents, asset: &str, decimals: u64) {
+    for claim in &claims.claimed {
+        let row = tables.create_row("reward_claims", [
+            ("evt_tx_hash", claim.evt_tx_hash.clone()),
+            ("evt_index", claim.evt_index.to_string()),
+        ]);
+        row.set("evt_block_number", claim.evt_block_number)
+            .set("airdrop_recipient", format!("0x{}", Hex(&claim.airdrop_recipient)))
+            .set("asset", asset)
+            .set("amount", claim.amount.parse::<BigInt>().unwrap_or_default().to_decimal(decimals));
+        if let Some(block_time) = &claim.evt_block_time {
+            row.set("evt_block_time", block_time);
+        }
+    }
+}
+
+// Outcome token balances from store_positions deltas (<wallet>:<token_id>) in their collateral's decimals,
+// keyed by wallet + token. balance stays NULL for a token of an unknown collateral
+pub fn position_rows(
+    tables: &mut Tables,
+    positions: &Deltas<DeltaBigInt>,
+    position_collaterals: &StoreGetProto<contract::CollateralToken>,
+    clock: &Clock,
+) {
+    for delta in &positions.deltas {
+        let Some((wallet, token_id)) = delta.key.split_once(':') else {
+            continue;
+        };
+        let row = tables.upsert_row("positions", [
+            ("wallet", wallet.to_string()),
+            ("token_id", token_id.to_string()),
+        ]);
+        row.set("block_number", clock.number);
+        if let Some(decimals) = collateral::position_decimals(position_collaterals, token_id) {
+            row.set("balance", delta.new_value.to_decimal(decimals as u64));
+        }
+        if let Some(timestamp) = &clock.timestamp {
+            row.set("updated_at", timestamp);
+        }
+    }
+}
+
+// Net USDC cash flow from store_wallet_cash deltas, share value is joined in SQL (see wallet_pnl_view)
+pub fn wallet_pnl_rows(tables: &mut Tables, wallet_cash: &Deltas<DeltaBigInt>, clock: &Clock) {
+    for delta in &wallet_cash.deltas {
+        let row = tables.upsert_row("wallet_pnl", &delta.key);
+        row.set("net_usdc", delta.new_value.to_decimal(6))
+            .set("block_number", clock.number);
+        if let Some(timestamp) = &clock.timestamp {
+            row.set("updated_at", timestamp);
+        }
+    }
+}
+
+// Collateral registry entries from store_collateral_tokens deltas, keyed by token address
+pub fn collateral_rows(tables: &mut Tables, collaterals: &Deltas<DeltaProto<contract::CollateralToken>>) {
+    for delta in &collaterals.deltas {
+        let collateral = &delta.new_value;
+        tables.upsert_row("collateral_tokens", format!("0x{}", Hex(&collateral.address)))
+            .set("symbol", &collateral.symbol)
+            .set("decimals", collateral.decimals)
+            .set("source", &collateral.source)
+            .set("first_seen_block", collateral.first_seen_block);
+    }
+}
@@ -0,0 +1,74 @@
+// Per-block diagnostics (map_diagnostics): what each log was read as, and the decoded events modules left out
+use crate::abi;
+use crate::events;
+use crate::network::NetworkProfile;
+use crate::pb::contract::v1 as contract;
+use std::collections::BTreeMap;
+use substreams_ethereum::pb::eth::v2 as eth;
+
+// DroppedRecords reasons
+pub const EXCLUDED_ADDRESS: &str = "excluded_address";
+pub const USDC_NOT_TRADING_RELATED: &str = "usdc_not_trading_related";
+pub const WALLET_FILTER: &str = "wallet_filter";
+
+// Records dropped by map_pure_dune_pnl, by reason
+#[derive(Default)]
+pub struct Drops(BTreeMap<&'static str, u64>);
+
+impl Drops {
+    pub fn add(&mut self, reason: &'static str, count: usize) {
+        if count > 0 {
+            *self.0.entry(reason).or_default() += count as u64;
+        }
+    }
+
+    pub fn records(&self, module: &str) -> Vec<contract::DroppedRecords> {
+        self.0.iter()
+            .map(|(reason, count)| contract::DroppedRecords { module: module.to_string(), reason: reason.to_string(), count: *count })
+            .collect()
+    }
+}
+
+// Counts over the logs that took effect, with the records map_pure_dune_pnl dropped while decoding the same block
+pub fn block_diagnostics(network: &NetworkProfile, blk: &eth::Block, dropped_records: Vec<contract::DroppedRecords>) -> contract::BlockDiagnostics {
+    let mut events: BTreeMap<&str, contract::EventDiagnostics> = BTreeMap::new();
+    let mut unknown_topics: BTreeMap<(&[u8], &[u8]), u64> = BTreeMap::new();
+    let mut logs_seen = 0;
+
+    for origin in events::block_logs(blk) {
+        let log = origin.log;
+        logs_seen += 1;
+        match abi::event_type(network, log) {
+            Some(event_type) => {
+                let counts = events.entry(event_type).or_insert_with(|| contract::EventDiagnostics {
+                    event_type: event_type.to_string(),
+                    ..Default::default()
+                });
+                counts.seen += 1;
+                if abi::route(network, log).is_some() {
+                    counts.decoded += 1;
+                } else {
+                    counts.failed += 1;
+                }
+            },
+            // Anonymous logs have no topic0, they are reported with an empty one
+            None if network.contracts().any(|contract| log.address == contract) => {
+                let topic0 = log.topics.first().map(Vec::as_slice).unwrap_or_default();
+                *unknown_topics.entry((&log.address, topic0)).or_default() += 1;
+            },
+            None => {},
+        }
+    }
+
+    contract::BlockDiagnostics {
+        block_number: blk.number,
+        block_timestamp: Some(blk.timestamp().to_owned()),
+        logs_seen,
+        logs_skipped: events::skipped_log_count(blk),
+        events: events.into_values().collect(),
+        unknown_topics: unknown_topics.into_iter()
+            .map(|((address, topic0), count)| contract::UnknownTopic { address: address.to_vec(), topic0: topic0.to_vec(), count })
+            .collect(),
+        dropped_records,
+    }
+}
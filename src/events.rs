@@ -0,0 +1,96 @@
+// Shared log extraction: every log of the block paired with the TransactionTrace it was emitted in,
+// so hash, transaction index and sender always come from the log's own transaction
+use crate::pb::contract::v1 as contract;
+use substreams::Hex;
+use substreams_ethereum::pb::eth::v2 as eth;
+
+pub struct BlockLog<'a> {
+    pub trace: &'a eth::TransactionTrace,
+    pub log: &'a eth::Log,
+}
+
+impl BlockLog<'_> {
+    // Hex without 0x, as stored in evt_tx_hash
+    pub fn tx_hash(&self) -> String {
+        Hex(&self.trace.hash).to_string()
+    }
+}
+
+// Logs that took effect, in block order: failed and reverted transactions contribute nothing,
+// and on extended blocks logs of calls whose state was reverted are left out as well
+pub fn block_logs(blk: &eth::Block) -> impl Iterator<Item = BlockLog<'_>> {
+    blk.transaction_traces.iter().flat_map(move |trace| {
+        transaction_logs(blk, trace).0.into_iter().map(move |log| BlockLog { trace, log })
+    })
+}
+
+// Logs dropped by block_logs, for observability
+pub fn skipped_log_count(blk: &eth::Block) -> u64 {
+    blk.transaction_traces.iter().map(|trace| transaction_logs(blk, trace).1 as u64).sum()
+}
+
+// Kept logs of one transaction and the number skipped
+fn transaction_logs<'a>(blk: &eth::Block, trace: &'a eth::TransactionTrace) -> (Vec<&'a eth::Log>, usize) {
+    let succeeded = trace.status == eth::TransactionTraceStatus::Succeeded as i32;
+
+    // Call logs carry state_reverted, receipts only exist for the transaction as a whole
+    if blk.detail_level == eth::block::DetailLevel::DetaillevelExtended as i32 && !trace.calls.is_empty() {
+        let (kept, reverted): (Vec<&eth::Call>, Vec<&eth::Call>) = trace.calls.iter()
+            .partition(|call| succeeded && !call.state_reverted);
+        let mut logs: Vec<&eth::Log> = kept.iter().flat_map(|call| &call.logs).collect();
+        logs.sort_by_key(|log| log.ordinal);
+        return (logs, reverted.iter().map(|call| call.logs.len()).sum());
+    }
+
+    let logs: Vec<&eth::Log> = trace.receipt.iter().flat_map(|receipt| &receipt.logs).collect();
+    if succeeded { (logs, 0) } else { (Vec::new(), logs.len()) }
+}
+
+// Records carrying the evt_* origin fields of the log they were decoded from
+pub trait EventRecord {
+    fn set_origin(&mut self, origin: &BlockLog, blk: &eth::Block);
+}
+
+macro_rules! event_records {
+    ($($record:ty),* $(,)?) => {
+        $(
+            impl EventRecord for $record {
+                fn set_origin(&mut self, origin: &BlockLog, blk: &eth::Block) {
+                    self.evt_tx_hash = origin.tx_hash();
+                    self.evt_tx_index = origin.trace.index;
+                    self.evt_tx_from = origin.trace.from.clone();
+                    self.evt_index = origin.log.block_index;
+                    self.evt_block_time = Some(blk.timestamp().to_owned());
+                    self.evt_block_number = blk.number;
+                }
+            }
+        )*
+    };
+}
+
+event_records!(
+    contract::Trade,
+    contract::TokenRegistered,
+    contract::NegRiskTokenRegistered,
+    contract::OrderFilled,
+    contract::OrdersMatched,
+    contract::FpmmFactoryCreation,
+    contract::Erc1155TransferSingle,
+    contract::Erc1155TransferBatch,
+    contract::Erc20Transfer,
+    contract::MerkleDistributorClaimed,
+    contract::CtfConditionPreparation,
+    contract::CtfConditionResolution,
+    contract::CtfPositionSplit,
+    contract::CtfPositionMerge,
+    contract::CtfPositionRedeem,
+    contract::CtfTransferSingle,
+    contract::CtfTransferBatch,
+    contract::UsdcTransfer,
+    contract::UsdcApproval,
+    contract::ExchangeOrderCancelled,
+    contract::ExchangeFeeCharged,
+    contract::ExchangeTradingPaused,
+    contract::ExchangeRoleChange,
+    contract::ExchangeFactoryUpdate,
+);
@@ -0,0 +1,124 @@
+// EntityChanges for a Substreams-powered subgraph, see schema.graphql and subgraph.yaml
+use crate::collateral;
+use crate::pb::contract::v1 as contract;
+use substreams::pb::substreams::Clock;
+use substreams::scalar::BigInt;
+use substreams::store::{DeltaBigInt, Deltas, StoreGetProto};
+use substreams::Hex;
+use substreams_entity_change::tables::Tables;
+
+// Markets touched in the block, id is the condition id
+pub fn market_entities(tables: &mut Tables, market_pnls: &contract::OnChainMarketPnLs) {
+    for market in &market_pnls.market_pnls {
+        let row = tables.update_row("Market", &market.condition_id);
+        row.set("questionId", &market.question_id)
+            .set_bigdecimal("totalVolume", &market.total_volume)
+            .set_bigint("totalTrades", &market.total_trades)
+            .set_bigdecimal("totalFees", &market.total_fees)
+            .set("isNegRisk", market.is_neg_risk)
+            .set("lastUpdatedBlock", market.block_number);
+        if !market.resolution_price.is_empty() {
+            row.set("winningOutcome", &market.winning_outcome)
+                .set_bigdecimal("resolutionPrice", &market.resolution_price);
+        }
+        if let Some(created_at) = &market.created_at {
+            row.set("createdAt", created_at.seconds);
+        }
+        if let Some(resolved_at) = &market.resolved_at {
+            row.set("resolvedAt", resolved_at.seconds);
+        }
+    }
+}
+
+// One Trade per enriched fill, id is <tx hash>-<log index>
+pub fn trade_entities(tables: &mut Tables, trades: &contract::Trades) {
+    for trade in &trades.trades {
+        let row = tables.create_row("Trade", format!("{}-{}", trade.evt_tx_hash, trade.evt_index));
+        row.set("transactionHash", &trade.evt_tx_hash)
+            .set("logIndex", trade.evt_index)
+            .set("blockNumber", trade.evt_block_number)
+            .set("timestamp", trade.evt_block_time.as_ref().map(|t| t.seconds).unwrap_or_default())
+            .set("venue", &trade.venue)
+            .set("exchange", &trade.exchange_address)
+            .set("orderHash", &trade.order_hash)
+            .set("maker", &trade.maker_address)
+            .set("taker", &trade.taker_address)
+            .set("makerSide", &trade.maker_side)
+            .set("takerSide", &trade.taker_side)
+            .set("tokenId", &trade.token_id)
+            .set("outcomeIndex", trade.outcome_index)
+            .set("market", &trade.condition_id)
+            .set_bigdecimal("price", &trade.price)
+            .set_bigdecimal("shares", &trade.shares)
+            .set_bigdecimal("usdcAmount", &trade.usdc_amount)
+            .set_bigdecimal("feeUsdc", &trade.fee_usdc)
+            .set("isTakerOrder", trade.is_taker_order);
+    }
+}
+
+// CTF PayoutRedemption events, id is <tx hash>-<log index>, payout in the redeemed collateral's decimals. payout is
+// required, so redemptions of an unknown collateral are left out
+pub fn redemption_entities(tables: &mut Tables, ctf_events: &contract::CtfEvents, collaterals: &StoreGetProto<contract::CollateralToken>) {
+    for redemption in &ctf_events.position_redemptions {
+        let Some(decimals) = collateral::decimals(collaterals, &redemption.collateral_token) else {
+            continue;
+        };
+        let row = tables.create_row("Redemption", format!("{}-{}", redemption.evt_tx_hash, redemption.evt_index));
+        row.set("transactionHash", &redemption.evt_tx_hash)
+            .set("logIndex", redemption.evt_index)
+            .set("blockNumber", redemption.evt_block_number)
+            .set("timestamp", redemption.evt_block_time.as_ref().map(|t| t.seconds).unwrap_or_default())
+            .set("redeemer", format!("0x{}", Hex(&redemption.redeemer)))
+            .set("market", format!("0x{}", Hex(&redemption.condition_id)))
+            .set("indexSets", &redemption.index_sets)
+            .set("payout", redemption.payout.parse::<BigInt>().unwrap_or_default().to_decimal(decimals as u64));
+    }
+}
+
+// Merkle distributor claims, id is <tx hash>-<log index>
+pub fn reward_claim_entities(tables: &mut Tables, claims: &contract::MerkleDistributorEvents, asset: &str, decimals: u64) {
+    for claim in &claims.claimed {
+        let row = tables.create_row("RewardClaim", format!("{}-{}", claim.evt_tx_hash, claim.evt_index));
+        row.set("transactionHash", &claim.evt_tx_hash)
+            .set("logIndex", claim.evt_index)
+            .set("blockNumber", claim.evt_block_number)
+            .set("timestamp", claim.evt_block_time.as_ref().map(|t| t.seconds).unwrap_or_default())
+            .set("recipient", format!("0x{}", Hex(&claim.airdrop_recipient)))
+            .set("asset", asset)
+            .set("amount", claim.amount.parse::<BigInt>().unwrap_or_default().to_decimal(decimals));
+    }
+}
+
+// Positions from store_positions deltas (<wallet>:<token_id>), id is <wallet>-<token_id>, balance in the collateral's decimals,
+// positions in an unknown collateral are left out
+pub fn position_entities(
+    tables: &mut Tables,
+    positions: &Deltas<DeltaBigInt>,
+    position_collaterals: &StoreGetProto<contract::CollateralToken>,
+    clock: &Clock,
+) {
+    for delta in &positions.deltas {
+        let Some((wallet, token_id)) = delta.key.split_once(':') else {
+            continue;
+        };
+        let Some(decimals) = collateral::position_decimals(position_collaterals, token_id) else {
+            continue;
+        };
+        tables.update_row("Position", format!("{}-{}", wallet, token_id))
+            .set("account", wallet)
+            .set("tokenId", token_id)
+            .set("balance", delta.new_value.to_decimal(decimals as u64))
+            .set("lastUpdatedBlock", clock.number);
+        tables.update_row("Account", wallet)
+            .set("lastUpdatedBlock", clock.number);
+    }
+}
+
+// Account net USDC from store_wallet_cash deltas, id is the wallet
+pub fn account_entities(tables: &mut Tables, wallet_cash: &Deltas<DeltaBigInt>, clock: &Clock) {
+    for delta in &wallet_cash.deltas {
+        tables.update_row("Account", &delta.key)
+            .set("netUsdc", delta.new_value.to_decimal(6))
+            .set("lastUpdatedBlock", clock.number);
+    }
+}
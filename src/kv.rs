@@ -0,0 +1,71 @@
+// KVOperations for substreams-sink-kv, values are the protobuf-encoded latest records
+use crate::collateral;
+use crate::pb::contract::v1 as contract;
+use crate::pb::sf::substreams::sink::kv::v1::{kv_operation, KvOperation, KvOperations};
+use prost::Message;
+use substreams::pb::substreams::Clock;
+use substreams::store::{DeltaBigInt, Deltas, StoreGetProto};
+
+fn set(operations: &mut KvOperations, key: String, value: Vec<u8>, ordinal: u64) {
+    operations.operations.push(KvOperation {
+        key,
+        value,
+        ordinal,
+        r#type: kv_operation::Type::Set.into(),
+    });
+}
+
+fn delete(operations: &mut KvOperations, key: String, ordinal: u64) {
+    operations.operations.push(KvOperation {
+        key,
+        value: Vec::new(),
+        ordinal,
+        r#type: kv_operation::Type::Delete.into(),
+    });
+}
+
+// market:<condition> -> OnChainMarketPnL
+pub fn market_operations(operations: &mut KvOperations, market_pnls: &contract::OnChainMarketPnLs) {
+    for market in &market_pnls.market_pnls {
+        set(operations, format!("market:{}", market.condition_id), market.encode_to_vec(), 0);
+    }
+}
+
+// wallet:<addr> -> WalletPnL from map_wallet_pnl, net USDC plus open positions at their latest price
+pub fn wallet_operations(operations: &mut KvOperations, wallet_pnls: &contract::WalletPnLs) {
+    for wallet_pnl in &wallet_pnls.wallet_pnls {
+        set(operations, format!("wallet:{}", wallet_pnl.wallet), wallet_pnl.encode_to_vec(), 0);
+    }
+}
+
+// position:<addr>:<token> -> PositionSnapshot from store_positions deltas, balance in the collateral's decimals,
+// closed positions are deleted and positions in an unknown collateral are not written
+pub fn position_operations(
+    operations: &mut KvOperations,
+    positions: &Deltas<DeltaBigInt>,
+    position_collaterals: &StoreGetProto<contract::CollateralToken>,
+    clock: &Clock,
+) {
+    for delta in &positions.deltas {
+        let Some((wallet, token_id)) = delta.key.split_once(':') else {
+            continue;
+        };
+        let key = format!("position:{}", delta.key);
+        if delta.new_value.is_zero() {
+            delete(operations, key, delta.ordinal);
+            continue;
+        }
+        let Some(decimals) = collateral::position_decimals(position_collaterals, token_id) else {
+            continue;
+        };
+
+        let snapshot = contract::PositionSnapshot {
+            wallet: wallet.to_string(),
+            token_id: token_id.to_string(),
+            balance: delta.new_value.to_decimal(decimals as u64).to_string(),
+            block_number: clock.number,
+            updated_at: clock.timestamp,
+        };
+        set(operations, key, snapshot.encode_to_vec(), delta.ordinal);
+    }
+}
@@ -1,151 +1,139 @@
+// Handlers taking params get a raw host pointer in their generated wasm export
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
 mod abi;
+mod collateral;
+mod db;
+mod diagnostics;
+mod events;
+mod graph;
+mod kv;
+#[cfg(not(target_arch = "wasm32"))]
+mod memory_store;
+mod network;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod offline;
+mod params;
+#[cfg(test)]
+mod replay;
+mod v2;
+#[allow(dead_code, clippy::enum_variant_names)]
 mod pb;
-use hex_literal::hex;
+use events::EventRecord;
 use pb::contract::v1 as contract;
+use substreams::pb::substreams::store_delta::Operation;
+use substreams::pb::substreams::Clock;
+use substreams::scalar::{BigDecimal, BigInt};
+use substreams::store::{Appender, DeltaBigInt, DeltaInt64, DeltaProto, Deltas, StoreAdd, StoreAddBigInt, StoreAppend, StoreGet, StoreGetArray, StoreGetBigDecimal, StoreGetBigInt, StoreGetProto, StoreNew, StoreSet, StoreSetBigDecimal, StoreSetIfNotExists, StoreSetIfNotExistsInt64, StoreSetIfNotExistsProto, StoreSetInt64, StoreSetProto};
 use substreams::Hex;
+use substreams_database_change::pb::database::DatabaseChanges;
+use substreams_database_change::tables::Tables;
+use substreams_entity_change::pb::entity::EntityChanges;
 use substreams_ethereum::pb::eth::v2 as eth;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 #[allow(unused_imports)]
 use num_traits::cast::ToPrimitive;
 
 substreams_ethereum::init!();
 
-// Contract addresses from Dune query - EXACT MATCH
-const CTF_CONTRACT: [u8; 20] = hex!("4d97dcd97ec945f40cf65f87097ace5ea0476045");
-const CTF_EXCHANGE_CONTRACT: [u8; 20] = hex!("4bfb41d5b3570defd03c39a9a4d8de6bd8b8982e");
-const NEG_RISK_CTF_EXCHANGE: [u8; 20] = hex!("C5d563A36AE78145C45a50134d48A1215220f80a");
-const FPMM_FACTORY_CONTRACT: [u8; 20] = hex!("a5ef39c3d3e10d0b270233af41cac69796b12966");
-const USDC_CONTRACT: [u8; 20] = hex!("2791bca1f2de4661ed88a30c99a7a9449aa84174");
-const UMA_MERKLE_DISTRIBUTOR: [u8; 20] = hex!("3a3bd7bb9528e159577f7c2e685cc81a765002e2");
-const USDC_MERKLE_DISTRIBUTOR: [u8; 20] = hex!("c288480574783BD7615170660d71753378159c47");
-
-// Event signatures
-const TRANSFER_SINGLE_SIG: [u8; 32] = hex!("c3d58168c5ae7397731d063d5bbf3d657854427343f4c083240f7aacaa2d0f62");
-const TRANSFER_BATCH_SIG: [u8; 32] = hex!("4a39dc06b4d0e7966e8548a714ca43c1363dc4f7197e0d4a342b5f78a2dfb6b0");
-const TRANSFER_SIG: [u8; 32] = hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
-const CLAIMED_SIG: [u8; 32] = hex!("4c209b5fc8ad50758f13e2e1088ba56a560dff690a1c6fef26394f4c03821c4f");
-const QUESTION_INITIALIZED_SIG: [u8; 32] = hex!("2aac65a22b07e92208fb8fb75a7e3eba7a57064d03f620a427ce3e3c222762d0");
-
-// Excluded addresses from Dune query - EXACT MATCH
-const EXCLUDED_ADDRESSES: [&str; 7] = [
-    "0x4d97dcd97ec945f40cf65f87097ace5ea0476045", // CTF Contract
-    "0x4bfb41d5b3570defd03c39a9a4d8de6bd8b8982e", // CTF Exchange
-    "0x78769D50Be1763ed1CA0D5E878D93f05aabff29e", // Neg Risk Fee Module
-    "0x3a3bd7bb9528e159577f7c2e685cc81a765002e2", // UMA Merkle Distributor
-    "0xa5ef39c3d3e10d0b270233af41cac69796b12966", // FPMM Factory
-    "0xA2bD9CC3e04996Ca683C834E4D86A016f6bbDE5A", // Additional excluded
-    "0x0000000000000000000000000000000000000000", // Zero address
-];
-
 // 1. CTF Exchange TokenRegistered Events (Dune: polymarket_polygon.CTFExchange_evt_TokenRegistered)
 #[substreams::handlers::map]
-fn map_ctf_exchange_token_registered(blk: eth::Block) -> Result<contract::TokenRegisteredEvents, substreams::errors::Error> {
+fn map_ctf_exchange_token_registered(params: String, blk: eth::Block) -> Result<contract::TokenRegisteredEvents, substreams::errors::Error> {
+    Ok(ctf_token_registrations(params::Params::parse(&params)?.network, &blk))
+}
+
+fn ctf_token_registrations(network: &network::NetworkProfile, blk: &eth::Block) -> contract::TokenRegisteredEvents {
     let mut events = contract::TokenRegisteredEvents::default();
 
-    for receipt in blk.receipts() {
-        for log in &receipt.receipt.logs {
-            if log.address == CTF_EXCHANGE_CONTRACT {
-                // Decode TokenRegistered event
-                if let Some(mut decoded) = abi::decode_token_registered(log) {
-                    decoded.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                    decoded.evt_block_time = Some(blk.timestamp().to_owned());
-                    decoded.evt_block_number = blk.number;
-                    events.token_registered.push(decoded);
-                }
+    for origin in events::block_logs(blk) {
+        let log = origin.log;
+        if log.address == network.ctf_exchange {
+            // Decode TokenRegistered event
+            if let Some(abi::DecodedEvent::TokenRegistered(mut decoded)) = abi::route(network, log) {
+                decoded.set_origin(&origin, blk);
+                events.token_registered.push(decoded);
             }
         }
     }
 
-    Ok(events)
+    events
 }
 
 // 2. NegRisk CTF Exchange TokenRegistered Events (Dune: polymarket_polygon.NegRiskCtfExchange_evt_TokenRegistered)
 #[substreams::handlers::map]
-fn map_neg_risk_ctf_exchange_token_registered(blk: eth::Block) -> Result<contract::NegRiskTokenRegisteredEvents, substreams::errors::Error> {
+fn map_neg_risk_ctf_exchange_token_registered(params: String, blk: eth::Block) -> Result<contract::NegRiskTokenRegisteredEvents, substreams::errors::Error> {
+    Ok(neg_risk_token_registrations(params::Params::parse(&params)?.network, &blk))
+}
+
+fn neg_risk_token_registrations(network: &network::NetworkProfile, blk: &eth::Block) -> contract::NegRiskTokenRegisteredEvents {
     let mut events = contract::NegRiskTokenRegisteredEvents::default();
 
-    for receipt in blk.receipts() {
-        for log in &receipt.receipt.logs {
-            if log.address == NEG_RISK_CTF_EXCHANGE {
-                // Decode NegRisk TokenRegistered event
-                if let Some(mut decoded) = abi::decode_neg_risk_token_registered(log) {
-                    decoded.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                    decoded.evt_block_time = Some(blk.timestamp().to_owned());
-                    decoded.evt_block_number = blk.number;
-                    events.neg_risk_token_registered.push(decoded);
-                }
+    for origin in events::block_logs(blk) {
+        let log = origin.log;
+        if log.address == network.neg_risk_ctf_exchange {
+            // Decode NegRisk TokenRegistered event
+            if let Some(abi::DecodedEvent::TokenRegistered(mut decoded)) = abi::route(network, log) {
+                decoded.set_origin(&origin, blk);
+                events.neg_risk_token_registered.push(abi::neg_risk_token_registered(decoded));
             }
         }
     }
 
-    Ok(events)
+    events
 }
 
 // 3. Fixed Product Market Maker Factory Creation (Dune: polymarketfactory_polygon.FixedProductMarketMakerFactory_evt_FixedProductMarketMakerCreation)
 #[substreams::handlers::map]
-fn map_fpmm_factory_creation(blk: eth::Block) -> Result<contract::FpmmFactoryEvents, substreams::errors::Error> {
+fn map_fpmm_factory_creation(params: String, blk: eth::Block) -> Result<contract::FpmmFactoryEvents, substreams::errors::Error> {
+    Ok(fpmm_creations(params::Params::parse(&params)?.network, &blk))
+}
+
+fn fpmm_creations(network: &network::NetworkProfile, blk: &eth::Block) -> contract::FpmmFactoryEvents {
     let mut events = contract::FpmmFactoryEvents::default();
 
-    for receipt in blk.receipts() {
-        for log in &receipt.receipt.logs {
-            if log.address == FPMM_FACTORY_CONTRACT {
-                // Decode FixedProductMarketMakerCreation event
-                if let Some(mut decoded) = abi::decode_fpmm_creation(log) {
-                    decoded.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                    decoded.evt_block_time = Some(blk.timestamp().to_owned());
-                    decoded.evt_block_number = blk.number;
-                    events.factory_creations.push(decoded);
-                }
-            }
+    for origin in events::block_logs(blk) {
+        let log = origin.log;
+        // Decode FixedProductMarketMakerCreation event
+        if let Some(abi::DecodedEvent::FpmmCreation(mut decoded)) = abi::route(network, log) {
+            decoded.set_origin(&origin, blk);
+            events.factory_creations.push(decoded);
         }
     }
 
-    Ok(events)
+    events
 }
 
 // 4. CTF Exchange OrderFilled Events (Dune: polymarket_polygon.CTFExchange_evt_OrderFilled)
 #[substreams::handlers::map]
-fn map_ctf_exchange_order_filled(blk: eth::Block) -> Result<contract::OrderFilledEvents, substreams::errors::Error> {
-    let mut events = contract::OrderFilledEvents::default();
-
-    for receipt in blk.receipts() {
-        for log in &receipt.receipt.logs {
-            if log.address == CTF_EXCHANGE_CONTRACT {
-                // Decode OrderFilled event
-                if let Some(mut decoded) = abi::decode_order_filled(log) {
-                    decoded.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                    decoded.evt_block_time = Some(blk.timestamp().to_owned());
-                    decoded.evt_block_number = blk.number;
-                    events.order_filled.push(decoded);
-                }
-            }
-        }
-    }
-
-    Ok(events)
+fn map_ctf_exchange_order_filled(params: String, blk: eth::Block) -> Result<contract::OrderFilledEvents, substreams::errors::Error> {
+    let network = params::Params::parse(&params)?.network;
+    Ok(order_fills(network, &network.ctf_exchange, &blk))
 }
 
 // 5. NegRisk CTF Exchange OrderFilled Events (Dune: polymarket_polygon.NegRiskCtfExchange_evt_OrderFilled)
 #[substreams::handlers::map]
-fn map_neg_risk_ctf_exchange_order_filled(blk: eth::Block) -> Result<contract::OrderFilledEvents, substreams::errors::Error> {
+fn map_neg_risk_ctf_exchange_order_filled(params: String, blk: eth::Block) -> Result<contract::OrderFilledEvents, substreams::errors::Error> {
+    let network = params::Params::parse(&params)?.network;
+    Ok(order_fills(network, &network.neg_risk_ctf_exchange, &blk))
+}
+
+// OrderFilled logs of one exchange, CTF or NegRisk
+fn order_fills(network: &network::NetworkProfile, exchange: &[u8], blk: &eth::Block) -> contract::OrderFilledEvents {
     let mut events = contract::OrderFilledEvents::default();
 
-    for receipt in blk.receipts() {
-        for log in &receipt.receipt.logs {
-            if log.address == NEG_RISK_CTF_EXCHANGE {
-                // Decode NegRisk OrderFilled event
-                if let Some(mut decoded) = abi::decode_order_filled(log) {
-                    decoded.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                    decoded.evt_block_time = Some(blk.timestamp().to_owned());
-                    decoded.evt_block_number = blk.number;
-                    events.order_filled.push(decoded);
-                }
+    for origin in events::block_logs(blk) {
+        let log = origin.log;
+        if log.address == exchange {
+            // Decode OrderFilled event
+            if let Some(abi::DecodedEvent::OrderFilled(mut decoded)) = abi::route(network, log) {
+                decoded.set_origin(&origin, blk);
+                events.order_filled.push(decoded);
             }
         }
     }
 
-    Ok(events)
+    events
 }
 
 // 6. ERC1155 TransferSingle Events (Dune: erc1155_polygon.evt_TransferSingle)
@@ -153,17 +141,12 @@ fn map_neg_risk_ctf_exchange_order_filled(blk: eth::Block) -> Result<contract::O
 fn map_erc1155_transfer_single(blk: eth::Block) -> Result<contract::Erc1155TransferSingleEvents, substreams::errors::Error> {
     let mut events = contract::Erc1155TransferSingleEvents::default();
 
-    for receipt in blk.receipts() {
-        for log in &receipt.receipt.logs {
-            // Check for TransferSingle event signature
-            if log.topics.len() >= 4 && log.topics[0] == TRANSFER_SINGLE_SIG {
-                if let Some(mut decoded) = abi::decode_erc1155_transfer_single(log) {
-                    decoded.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                    decoded.evt_block_time = Some(blk.timestamp().to_owned());
-                    decoded.evt_block_number = blk.number;
-                    events.transfer_single.push(decoded);
-                }
-            }
+    for origin in events::block_logs(&blk) {
+        let log = origin.log;
+        // TransferSingle from any ERC1155 contract
+        if let Some(abi::DecodedEvent::Erc1155TransferSingle(mut decoded)) = abi::route_by_topic(log) {
+            decoded.set_origin(&origin, &blk);
+            events.transfer_single.push(decoded);
         }
     }
 
@@ -175,17 +158,12 @@ fn map_erc1155_transfer_single(blk: eth::Block) -> Result<contract::Erc1155Trans
 fn map_erc1155_transfer_batch(blk: eth::Block) -> Result<contract::Erc1155TransferBatchEvents, substreams::errors::Error> {
     let mut events = contract::Erc1155TransferBatchEvents::default();
 
-    for receipt in blk.receipts() {
-        for log in &receipt.receipt.logs {
-            // Check for TransferBatch event signature
-            if log.topics.len() >= 4 && log.topics[0] == TRANSFER_BATCH_SIG {
-                if let Some(mut decoded) = abi::decode_erc1155_transfer_batch(log) {
-                    decoded.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                    decoded.evt_block_time = Some(blk.timestamp().to_owned());
-                    decoded.evt_block_number = blk.number;
-                    events.transfer_batch.push(decoded);
-                }
-            }
+    for origin in events::block_logs(&blk) {
+        let log = origin.log;
+        // TransferBatch from any ERC1155 contract
+        if let Some(abi::DecodedEvent::Erc1155TransferBatch(mut decoded)) = abi::route_by_topic(log) {
+            decoded.set_origin(&origin, &blk);
+            events.transfer_batch.push(decoded);
         }
     }
 
@@ -197,17 +175,12 @@ fn map_erc1155_transfer_batch(blk: eth::Block) -> Result<contract::Erc1155Transf
 fn map_erc20_transfer(blk: eth::Block) -> Result<contract::Erc20TransferEvents, substreams::errors::Error> {
     let mut events = contract::Erc20TransferEvents::default();
 
-    for receipt in blk.receipts() {
-        for log in &receipt.receipt.logs {
-            // Check for Transfer event signature
-            if log.topics.len() >= 3 && log.topics[0] == TRANSFER_SIG {
-                if let Some(mut decoded) = abi::decode_erc20_transfer(log) {
-                    decoded.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                    decoded.evt_block_time = Some(blk.timestamp().to_owned());
-                    decoded.evt_block_number = blk.number;
-                    events.transfer.push(decoded);
-                }
-            }
+    for origin in events::block_logs(&blk) {
+        let log = origin.log;
+        // Transfer from any ERC20 contract
+        if let Some(abi::DecodedEvent::Erc20Transfer(mut decoded)) = abi::route_by_topic(log) {
+            decoded.set_origin(&origin, &blk);
+            events.transfer.push(decoded);
         }
     }
 
@@ -216,19 +189,17 @@ fn map_erc20_transfer(blk: eth::Block) -> Result<contract::Erc20TransferEvents,
 
 // 9. UMA Merkle Distributor Events (Dune: polymarket_uma_merkle_distributor_polygon.MerkleDistributor_evt_Claimed)
 #[substreams::handlers::map]
-fn map_uma_merkle_distributor(blk: eth::Block) -> Result<contract::MerkleDistributorEvents, substreams::errors::Error> {
+fn map_uma_merkle_distributor(params: String, blk: eth::Block) -> Result<contract::MerkleDistributorEvents, substreams::errors::Error> {
+    let network = params::Params::parse(&params)?.network;
     let mut events = contract::MerkleDistributorEvents::default();
 
-    for receipt in blk.receipts() {
-        for log in &receipt.receipt.logs {
-            if log.address == UMA_MERKLE_DISTRIBUTOR {
-                // Decode MerkleDistributor Claimed event
-                if let Some(mut decoded) = abi::decode_merkle_claimed(log) {
-                    decoded.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                    decoded.evt_block_time = Some(blk.timestamp().to_owned());
-                    decoded.evt_block_number = blk.number;
-                    events.claimed.push(decoded);
-                }
+    for origin in events::block_logs(&blk) {
+        let log = origin.log;
+        if log.address == network.uma_merkle_distributor {
+            // Decode MerkleDistributor Claimed event
+            if let Some(abi::DecodedEvent::MerkleClaimed(mut decoded)) = abi::route(network, log) {
+                decoded.set_origin(&origin, &blk);
+                events.claimed.push(decoded);
             }
         }
     }
@@ -238,19 +209,17 @@ fn map_uma_merkle_distributor(blk: eth::Block) -> Result<contract::MerkleDistrib
 
 // 10. USDC Merkle Distributor Events (Dune: polymarket_usdc_merkle_distributor_polygon.MerkleDistributor_evt_Claimed)
 #[substreams::handlers::map]
-fn map_usdc_merkle_distributor(blk: eth::Block) -> Result<contract::MerkleDistributorEvents, substreams::errors::Error> {
+fn map_usdc_merkle_distributor(params: String, blk: eth::Block) -> Result<contract::MerkleDistributorEvents, substreams::errors::Error> {
+    let network = params::Params::parse(&params)?.network;
     let mut events = contract::MerkleDistributorEvents::default();
 
-    for receipt in blk.receipts() {
-        for log in &receipt.receipt.logs {
-            if log.address == USDC_MERKLE_DISTRIBUTOR {
-                // Decode MerkleDistributor Claimed event
-                if let Some(mut decoded) = abi::decode_merkle_claimed(log) {
-                    decoded.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                    decoded.evt_block_time = Some(blk.timestamp().to_owned());
-                    decoded.evt_block_number = blk.number;
-                    events.claimed.push(decoded);
-                }
+    for origin in events::block_logs(&blk) {
+        let log = origin.log;
+        if log.address == network.usdc_merkle_distributor {
+            // Decode MerkleDistributor Claimed event
+            if let Some(abi::DecodedEvent::MerkleClaimed(mut decoded)) = abi::route(network, log) {
+                decoded.set_origin(&origin, &blk);
+                events.claimed.push(decoded);
             }
         }
     }
@@ -260,92 +229,39 @@ fn map_usdc_merkle_distributor(blk: eth::Block) -> Result<contract::MerkleDistri
 
 // 11. CTF Events (Dune: references CTF contract)
 #[substreams::handlers::map]
-fn map_ctf_events(blk: eth::Block) -> Result<contract::CtfEvents, substreams::errors::Error> {
-    let mut events = contract::CtfEvents::default();
-
-    for receipt in blk.receipts() {
-        for log in &receipt.receipt.logs {
-            if log.address == CTF_CONTRACT {
-                // Decode various CTF events
-                if let Some(decoded) = abi::decode_ctf_events(log) {
-                    match decoded {
-                        abi::CtfEventType::ConditionPreparation(mut evt) => {
-                            evt.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                            evt.evt_block_time = Some(blk.timestamp().to_owned());
-                            evt.evt_block_number = blk.number;
-                            events.condition_preparations.push(evt);
-                        },
-                        abi::CtfEventType::ConditionResolution(mut evt) => {
-                            evt.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                            evt.evt_block_time = Some(blk.timestamp().to_owned());
-                            evt.evt_block_number = blk.number;
-                            events.condition_resolutions.push(evt);
-                        },
-                        abi::CtfEventType::PositionSplit(mut evt) => {
-                            evt.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                            evt.evt_block_time = Some(blk.timestamp().to_owned());
-                            evt.evt_block_number = blk.number;
-                            events.position_splits.push(evt);
-                        },
-                        abi::CtfEventType::PositionMerge(mut evt) => {
-                            evt.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                            evt.evt_block_time = Some(blk.timestamp().to_owned());
-                            evt.evt_block_number = blk.number;
-                            events.position_merges.push(evt);
-                        },
-                        abi::CtfEventType::PositionRedeem(mut evt) => {
-                            evt.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                            evt.evt_block_time = Some(blk.timestamp().to_owned());
-                            evt.evt_block_number = blk.number;
-                            events.position_redemptions.push(evt);
-                        },
-                        abi::CtfEventType::TransferSingle(mut evt) => {
-                            evt.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                            evt.evt_block_time = Some(blk.timestamp().to_owned());
-                            evt.evt_block_number = blk.number;
-                            events.transfer_singles.push(evt);
-                        },
-                        abi::CtfEventType::TransferBatch(mut evt) => {
-                            evt.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                            evt.evt_block_time = Some(blk.timestamp().to_owned());
-                            evt.evt_block_number = blk.number;
-                            events.transfer_batches.push(evt);
-                        },
-                    }
-                }
-            }
-        }
-    }
-
-    Ok(events)
+fn map_ctf_events(params: String, blk: eth::Block) -> Result<contract::CtfEvents, substreams::errors::Error> {
+    Ok(ctf_events(params::Params::parse(&params)?.network, &blk))
 }
 
 // 12. USDC Events (Dune: erc20_polygon.evt_Transfer where contract_address = 0x2791bca1f2de4661ed88a30c99a7a9449aa84174)
 #[substreams::handlers::map]
-fn map_usdc_events(blk: eth::Block) -> Result<contract::UsdcEvents, substreams::errors::Error> {
+fn map_usdc_events(params: String, blk: eth::Block) -> Result<contract::UsdcEvents, substreams::errors::Error> {
+    let network = params::Params::parse(&params)?.network;
     let mut events = contract::UsdcEvents::default();
 
-    for receipt in blk.receipts() {
-        for log in &receipt.receipt.logs {
-            if log.address == USDC_CONTRACT {
-                // Decode USDC Transfer and Approval events
-                if let Some(decoded) = abi::decode_usdc_events(log) {
-                    match decoded {
-                        abi::UsdcEventType::Transfer(mut evt) => {
-                            evt.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                            evt.evt_block_time = Some(blk.timestamp().to_owned());
-                            evt.evt_block_number = blk.number;
-                            events.transfers.push(evt);
-                        },
-                        abi::UsdcEventType::Approval(mut evt) => {
-                            evt.evt_tx_hash = Hex(&receipt.transaction.hash).to_string();
-                            evt.evt_block_time = Some(blk.timestamp().to_owned());
-                            evt.evt_block_number = blk.number;
-                            events.approvals.push(evt);
-                        },
-                    }
-                }
-            }
+    for origin in events::block_logs(&blk) {
+        let log = origin.log;
+        if log.address != network.usdc {
+            continue;
+        }
+
+        // USDC Transfer and Approval events
+        match abi::route(network, log) {
+            Some(abi::DecodedEvent::Erc20Transfer(transfer)) => {
+                let mut evt = contract::UsdcTransfer {
+                    from: transfer.from,
+                    to: transfer.to,
+                    value: transfer.value,
+                    ..Default::default()
+                };
+                evt.set_origin(&origin, &blk);
+                events.transfers.push(evt);
+            },
+            Some(abi::DecodedEvent::UsdcApproval(mut evt)) => {
+                evt.set_origin(&origin, &blk);
+                events.approvals.push(evt);
+            },
+            _ => {},
         }
     }
 
@@ -354,10 +270,15 @@ fn map_usdc_events(blk: eth::Block) -> Result<contract::UsdcEvents, substreams::
 
 // 13. Pure Dune Query P&L Data - EXACT MATCH TO DUNE QUERY (NO SIMULATION)
 #[substreams::handlers::map]
-fn map_pure_dune_pnl(blk: eth::Block) -> Result<contract::PureDunePnL, substreams::errors::Error> {
-    substreams::log::info!("Processing block {}", blk.number);
-    substreams::log::info!("Block has {} receipts", blk.receipts().count());
-    
+fn map_pure_dune_pnl(params: String, blk: eth::Block) -> Result<contract::PureDunePnL, substreams::errors::Error> {
+    let params = params::Params::parse(&params)?;
+    Ok(pure_dune_pnl(&params, &blk))
+}
+
+// The map_pure_dune_pnl output of one block, also run by the golden tests. Decoded events it leaves out are counted
+// in dropped_records for map_diagnostics
+fn pure_dune_pnl(params: &params::Params, blk: &eth::Block) -> contract::PureDunePnL {
+    let mut drops = diagnostics::Drops::default();
     let mut pnl_data = contract::PureDunePnL {
         total_users: "0".to_string(),
         total_volume: "0".to_string(),
@@ -365,6 +286,7 @@ fn map_pure_dune_pnl(blk: eth::Block) -> Result<contract::PureDunePnL, substream
         total_losses: "0".to_string(),
         block_number: blk.number,
         block_timestamp: Some(blk.timestamp().to_owned()),
+        skipped_logs: events::skipped_log_count(blk),
         ..Default::default()
     };
 
@@ -385,381 +307,375 @@ fn map_pure_dune_pnl(blk: eth::Block) -> Result<contract::PureDunePnL, substream
     let mut question_metadata: HashMap<String, String> = HashMap::new();
     
     // Track price data from OrderFilled events (like subgraph price feeds)
-    let mut latest_prices: HashMap<String, f64> = HashMap::new();
+    let mut latest_prices: HashMap<String, BigDecimal> = HashMap::new();
+
+    // Track USDC notional of maker fills (raw 6-decimal units) for total_volume
+    let mut total_volume = BigInt::zero();
+
+    // Transactions with a Claimed at the USDC distributor, their USDC transfer is already counted as that claim
+    let usdc_claim_tx_hashes: std::collections::HashSet<String> = events::block_logs(blk)
+        .filter(|origin| origin.log.address == params.network.usdc_merkle_distributor)
+        .filter(|origin| origin.log.topics.first().is_some_and(|topic0| topic0.as_slice() == abi::CLAIMED_SIG))
+        .map(|origin| format!("0x{}", origin.tx_hash()))
+        .collect();
 
     // Process all events to build Dune query compatible data
-    let receipts: Vec<_> = blk.receipts().collect();
-    let transactions: Vec<_> = blk.transactions().collect();
-    
-    substreams::log::info!("Processing {} receipts and {} transactions", receipts.len(), transactions.len());
-    
-    for (receipt_index, receipt) in receipts.iter().enumerate() {
-        // Get the actual transaction hash from the block
-        let tx_hash = if receipt_index < transactions.len() {
-            format!("0x{}", substreams::Hex(&transactions[receipt_index].hash).to_string())
-        } else {
-            "0x".to_string()
-        };
-        for log in &receipt.receipt.logs {
+    for origin in events::block_logs(blk) {
+        let log = origin.log;
+        let tx_hash = format!("0x{}", origin.tx_hash());
+        let tx_from = format!("0x{}", Hex(&origin.trace.from));
+        match abi::route(params.network, log) {
             // Process QuestionInitialized events to capture real metadata (like subgraph)
-            if log.topics.len() >= 2 && log.topics[0] == &QUESTION_INITIALIZED_SIG {
-                if let Some(question_data) = abi::decode_question_initialized(log) {
-                    let question_id = question_data.question_id.clone();
-                    let question_text = question_data.question.clone();
-                    question_metadata.insert(question_id, question_text);
-                }
-            }
-            
+            Some(abi::DecodedEvent::QuestionInitialized(question_data)) => {
+                question_metadata.insert(question_data.question_id, question_data.question);
+            },
+
             // Process FixedProductMarketMakerFactory events to track AMM markets (like Dune query amm_markets CTE)
-            if log.address == FPMM_FACTORY_CONTRACT {
-                if let Some(amm_address) = abi::decode_fixed_product_market_maker_creation(log) {
-                    amm_market_addresses.insert(amm_address);
-                }
-            }
-            
+            Some(abi::DecodedEvent::FpmmCreation(creation)) => {
+                amm_market_addresses.insert(Hex(&creation.fixed_product_market_maker).to_string());
+            },
+
             // Process TokenRegistered events to build market data (like Dune query markets CTE)
-            if log.address == CTF_EXCHANGE_CONTRACT || log.address == NEG_RISK_CTF_EXCHANGE {
-                if let Some(token_reg) = abi::decode_token_registered(log) {
-                    let condition_id = Hex(&token_reg.condition_id).to_string();
-                    let is_neg_risk = log.address == NEG_RISK_CTF_EXCHANGE;
-                    
-                    // Get question from condition_id (like Dune query joins with metadata)
-                    let question = question_metadata.get(&condition_id)
-                        .cloned()
-                        .unwrap_or_else(|| format!("Market for condition {}", condition_id));
-                    
-                    market_data.insert(condition_id.clone(), contract::DuneMarketData {
-                        condition_id: condition_id.clone(),
-                        token0: token_reg.token0.clone(),
-                        token1: token_reg.token1.clone(),
-                        question,
-                        is_neg_risk,
-                        created_at: Some(blk.timestamp().to_owned()),
-                        block_number: blk.number,
-                    });
-                }
-            }
-            
+            Some(abi::DecodedEvent::TokenRegistered(token_reg)) => {
+                let condition_id = Hex(&token_reg.condition_id).to_string();
+                let is_neg_risk = log.address == params.network.neg_risk_ctf_exchange;
+
+                // Get question from condition_id (like Dune query joins with metadata)
+                let question = question_metadata.get(&condition_id)
+                    .cloned()
+                    .unwrap_or_else(|| format!("Market for condition {}", condition_id));
+
+                market_data.insert(condition_id.clone(), contract::DuneMarketData {
+                    condition_id: condition_id.clone(),
+                    token0: token_reg.token0.clone(),
+                    token1: token_reg.token1.clone(),
+                    question,
+                    is_neg_risk,
+                    created_at: Some(blk.timestamp().to_owned()),
+                    block_number: blk.number,
+                });
+            },
+
             // Process ERC1155 TransferBatch events (like Dune query batch CTEs)
-            if log.topics.len() >= 4 && log.topics[0] == &TRANSFER_BATCH_SIG {
-                if let Some(mut batch_transfer) = abi::decode_erc1155_transfer_batch_ctf(log) {
-                    batch_transfer.evt_tx_hash = tx_hash.clone();
-                    let from_addr = Hex(&log.topics[2]).to_string();
-                    let to_addr = Hex(&log.topics[3]).to_string();
-                    
-                    // Process each token in the batch (like Dune query UNNEST)
-                    for (i, token_id) in batch_transfer.token_ids.iter().enumerate() {
-                        if i < batch_transfer.values.len() {
-                            let amount = &batch_transfer.values[i];
-                            let token_id_str = token_id.clone();
-                            let amount_str = amount.clone();
-                            
-                            // Convert to USDC units (divide by 1000000) as per Dune query
-                            let amount_usdc = if let Ok(val) = amount_str.parse::<f64>() {
-                                (val / 1_000_000.0).to_string()
-                            } else {
-                                "0".to_string()
-                            };
-                            
-                            // Track this transaction hash for USDC filtering
-                            trading_tx_hashes.insert(batch_transfer.evt_tx_hash.clone());
-                            
-                            // Process sends (negative amount)
-                            if from_addr != "0x0000000000000000000000000000000000000000" {
-                                if !is_excluded_address(&from_addr) {
-                                    token_transfers.push(contract::DuneTokenTransfer {
-                                        transaction_hash: batch_transfer.evt_tx_hash.clone(),
-                                        user_address: from_addr.clone(),
-                                        token_id: token_id_str.clone(),
-                                        amount: format!("-{}", amount_usdc),
-                                        transfer_type: "ERC1155_BATCH".to_string(),
-                                        block_timestamp: Some(blk.timestamp().to_owned()),
-                                        block_number: blk.number,
-                                    });
-                                }
-                            }
-                            
-                            // Process receives (positive amount)
-                            if to_addr != "0x0000000000000000000000000000000000000000" {
-                                if !is_excluded_address(&to_addr) {
-                                    token_transfers.push(contract::DuneTokenTransfer {
-                                        transaction_hash: batch_transfer.evt_tx_hash.clone(),
-                                        user_address: to_addr.clone(),
-                                        token_id: token_id_str.clone(),
-                                        amount: amount_usdc,
-                                        transfer_type: "ERC1155_BATCH".to_string(),
-                                        block_timestamp: Some(blk.timestamp().to_owned()),
-                                        block_number: blk.number,
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            
-            // Process ERC1155 transfers (sends/receives from Dune query)
-            if log.topics.len() >= 4 && log.topics[0] == &TRANSFER_SINGLE_SIG {
-                if let Some(mut transfer) = abi::decode_erc1155_transfer_single(log) {
-                    transfer.evt_tx_hash = tx_hash.clone();
-                    let from_addr = Hex(&log.topics[2]).to_string();
-                    let to_addr = Hex(&log.topics[3]).to_string();
-                    let token_id = transfer.id.clone();
-                    let amount = transfer.value.clone();
+            Some(abi::DecodedEvent::Erc1155TransferBatch(mut batch_transfer)) => {
+                batch_transfer.evt_tx_hash = tx_hash.clone();
+                let from_addr = Hex(&batch_transfer.from).to_string();
+                let to_addr = Hex(&batch_transfer.to).to_string();
+                let transfer_type = erc1155_transfer_type(&batch_transfer.from, &batch_transfer.to, "ERC1155_BATCH");
 
-                    // Convert to USDC units (divide by 1000000) as per Dune query
-                    let amount_usdc = if let Ok(val) = amount.parse::<f64>() {
-                        (val / 1_000_000.0).to_string()
-                    } else {
-                        "0".to_string()
-                    };
-
-                    // Track this transaction hash for USDC filtering (like Dune query)
-                    trading_tx_hashes.insert(transfer.evt_tx_hash.clone());
-
-                    // Process sends (negative amount)
-                    if from_addr != "0x0000000000000000000000000000000000000000" {
-                        if !is_excluded_address(&from_addr) {
-                            token_transfers.push(contract::DuneTokenTransfer {
-                                transaction_hash: transfer.evt_tx_hash.clone(),
-                                user_address: from_addr.clone(),
-                                token_id: token_id.clone(),
-                                amount: format!("-{}", amount_usdc),
-                                transfer_type: "ERC1155_SINGLE".to_string(),
-                                block_timestamp: Some(blk.timestamp().to_owned()),
-                                block_number: blk.number,
-                            });
-
-                            // Update user P&L
-                            update_user_pnl(&mut user_pnls, &from_addr, &token_id, &amount_usdc, &blk, false);
-                        }
-                    }
+                // Track this transaction hash for USDC filtering
+                trading_tx_hashes.insert(batch_transfer.evt_tx_hash.clone());
 
-                    // Process receives (positive amount)
-                    if to_addr != "0x0000000000000000000000000000000000000000" {
-                        if !is_excluded_address(&to_addr) {
-                            token_transfers.push(contract::DuneTokenTransfer {
-                                transaction_hash: transfer.evt_tx_hash.clone(),
-                                user_address: to_addr.clone(),
-                                token_id: token_id.clone(),
-                                amount: amount_usdc.clone(),
-                                transfer_type: "ERC1155_SINGLE".to_string(),
-                                block_timestamp: Some(blk.timestamp().to_owned()),
-                                block_number: blk.number,
-                            });
-
-                            // Update user P&L
-                            update_user_pnl(&mut user_pnls, &to_addr, &token_id, &amount_usdc, &blk, true);
-                        }
-                    }
-                }
-            }
+                // Process each token in the batch (like Dune query UNNEST)
+                for (token_id, value) in batch_transfer.ids.iter().zip(&batch_transfer.values) {
+                    // Convert to USDC units (divide by 1000000) as per Dune query
+                    let amount_usdc = raw_to_decimal(value, 6);
 
-            // Process additional USDC airdrops from specific distributor (like Dune query usdc_new CTE)
-            if log.topics.len() >= 3 && log.topics[0] == &TRANSFER_SIG && log.address == USDC_CONTRACT {
-                let from_addr = Hex(&log.topics[1]).to_string();
-                if from_addr == "0xc288480574783BD7615170660d71753378159c47" { // USDC Merkle Distributor
-                    if let Some(mut transfer) = abi::decode_erc20_transfer(log) {
-                        transfer.evt_tx_hash = tx_hash.clone();
-                        let to_addr = Hex(&log.topics[2]).to_string();
-                        let amount = transfer.value.clone();
-                        
-                        // Convert to USDC units (divide by 1000000) as per Dune query
-                        let amount_usdc = if let Ok(val) = amount.parse::<f64>() {
-                            (val / 1_000_000.0).to_string()
-                        } else {
-                            "0".to_string()
-                        };
-                        
-                        // Add as reward claim
-                        reward_claims.push(contract::DuneRewardClaim {
-                            transaction_hash: transfer.evt_tx_hash.clone(),
-                            log_index: 0,
+                    // Process sends (negative amount), nobody sends a mint
+                    if !is_zero_address(&batch_transfer.from) && !params.is_excluded(&batch_transfer.from) {
+                        token_transfers.push(contract::DuneTokenTransfer {
+                            transaction_hash: batch_transfer.evt_tx_hash.clone(),
+                            user_address: from_addr.clone(),
+                            token_id: token_id.clone(),
+                            amount: amount_usdc.neg().to_string(),
+                            transfer_type: transfer_type.to_string(),
                             block_timestamp: Some(blk.timestamp().to_owned()),
                             block_number: blk.number,
-                            airdrop_recipient: to_addr.clone(),
-                            asset: "usdc".to_string(),
-                            lc_amount: amount_usdc.clone(),
-                            usd_amount: amount_usdc.clone(),
-                            token_address: "0x".to_string(),
+                            log_index: log.block_index,
+                            tx_index: origin.trace.index,
+                            tx_from: tx_from.clone(),
                         });
-                        
-                        // Also add as token transfer
+
+                        // Update user P&L
+                        update_user_pnl(&mut user_pnls, &from_addr, token_id, &amount_usdc, blk, false);
+                    } else if !is_zero_address(&batch_transfer.from) {
+                        drops.add(diagnostics::EXCLUDED_ADDRESS, 1);
+                    }
+
+                    // Process receives (positive amount), nobody receives a burn
+                    if !is_zero_address(&batch_transfer.to) && !params.is_excluded(&batch_transfer.to) {
                         token_transfers.push(contract::DuneTokenTransfer {
-                            transaction_hash: transfer.evt_tx_hash.clone(),
+                            transaction_hash: batch_transfer.evt_tx_hash.clone(),
                             user_address: to_addr.clone(),
-                            token_id: "USDC".to_string(),
-                            amount: amount_usdc,
-                            transfer_type: "ERC20_AIRDROP".to_string(),
+                            token_id: token_id.clone(),
+                            amount: amount_usdc.to_string(),
+                            transfer_type: transfer_type.to_string(),
                             block_timestamp: Some(blk.timestamp().to_owned()),
                             block_number: blk.number,
+                            log_index: log.block_index,
+                            tx_index: origin.trace.index,
+                            tx_from: tx_from.clone(),
                         });
+
+                        // Update user P&L
+                        update_user_pnl(&mut user_pnls, &to_addr, token_id, &amount_usdc, blk, true);
+                    } else if !is_zero_address(&batch_transfer.to) {
+                        drops.add(diagnostics::EXCLUDED_ADDRESS, 1);
                     }
                 }
-            }
-            
-            // Process ERC20 transfers (USDC from Dune query) - ONLY trading-related transfers
-            if log.topics.len() >= 3 && log.topics[0] == &TRANSFER_SIG && log.address == USDC_CONTRACT {
-                if let Some(mut transfer) = abi::decode_erc20_transfer(log) {
-                    transfer.evt_tx_hash = tx_hash.clone();
-                    let from_addr = Hex(&log.topics[1]).to_string();
-                    let to_addr = Hex(&log.topics[2]).to_string();
-                    let amount = transfer.value.clone();
-
-                    // Only process USDC transfers related to trading (like Dune query erc20 CTE)
-                    let is_trading_related = trading_tx_hashes.contains(&tx_hash) ||
-                        from_addr == "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045" || // CTF Contract
-                        to_addr == "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045" ||   // CTF Contract
-                        amm_market_addresses.contains(&from_addr) ||  // AMM markets
-                        amm_market_addresses.contains(&to_addr);      // AMM markets
-
-                    if is_trading_related {
-                        // Convert to USDC units (divide by 1000000) as per Dune query
-                        let amount_usdc = if let Ok(val) = amount.parse::<f64>() {
-                            (val / 1_000_000.0).to_string()
-                        } else {
-                            "0".to_string()
-                        };
-
-                        // Process USDC transfers
-                        if !is_excluded_address(&from_addr) {
-                            token_transfers.push(contract::DuneTokenTransfer {
-                                transaction_hash: tx_hash.clone(),
-                                user_address: from_addr.clone(),
-                                token_id: "USDC".to_string(),
-                                amount: format!("-{}", amount_usdc),
-                                transfer_type: "ERC20".to_string(),
-                                block_timestamp: Some(blk.timestamp().to_owned()),
-                                block_number: blk.number,
-                            });
-                        }
-
-                        if !is_excluded_address(&to_addr) {
-                            token_transfers.push(contract::DuneTokenTransfer {
-                                transaction_hash: tx_hash.clone(),
-                                user_address: to_addr.clone(),
-                                token_id: "USDC".to_string(),
-                                amount: amount_usdc.clone(),
-                                transfer_type: "ERC20".to_string(),
-                                block_timestamp: Some(blk.timestamp().to_owned()),
-                                block_number: blk.number,
-                            });
-                        }
-                    }
-                }
-            }
+            },
 
-            // Process UMA Merkle Distributor claims (like Dune query uma CTE)
-            if log.address == UMA_MERKLE_DISTRIBUTOR && log.topics.len() >= 2 && log.topics[0] == &CLAIMED_SIG {
-                if let Some(mut uma_claim) = abi::decode_uma_merkle_claim(log) {
-                    uma_claim.transaction_hash = tx_hash.clone();
-                    uma_claim.block_timestamp = Some(blk.timestamp().to_owned());
-                    uma_claim.block_number = blk.number;
-                    reward_claims.push(uma_claim);
-                }
-            }
-            
-            // Process USDC Merkle Distributor claims (like Dune query usdc CTE)
-            if log.address == USDC_MERKLE_DISTRIBUTOR && log.topics.len() >= 2 && log.topics[0] == &CLAIMED_SIG {
-                if let Some(mut usdc_claim) = abi::decode_usdc_merkle_claim(log) {
-                    usdc_claim.transaction_hash = tx_hash.clone();
-                    usdc_claim.block_timestamp = Some(blk.timestamp().to_owned());
-                    usdc_claim.block_number = blk.number;
-                    reward_claims.push(usdc_claim);
+            // Process ERC1155 transfers (sends/receives from Dune query)
+            Some(abi::DecodedEvent::Erc1155TransferSingle(mut transfer)) => {
+                transfer.evt_tx_hash = tx_hash.clone();
+                let from_addr = Hex(&transfer.from).to_string();
+                let to_addr = Hex(&transfer.to).to_string();
+                let token_id = transfer.id.clone();
+                let transfer_type = erc1155_transfer_type(&transfer.from, &transfer.to, "ERC1155_SINGLE");
+
+                // Convert to USDC units (divide by 1000000) as per Dune query
+                let amount_usdc = raw_to_decimal(&transfer.value, 6);
+
+                // Track this transaction hash for USDC filtering (like Dune query)
+                trading_tx_hashes.insert(transfer.evt_tx_hash.clone());
+
+                // Process sends (negative amount), nobody sends a mint
+                if !is_zero_address(&transfer.from) && !params.is_excluded(&transfer.from) {
+                    token_transfers.push(contract::DuneTokenTransfer {
+                        transaction_hash: transfer.evt_tx_hash.clone(),
+                        user_address: from_addr.clone(),
+                        token_id: token_id.clone(),
+                        amount: amount_usdc.neg().to_string(),
+                        transfer_type: transfer_type.to_string(),
+                        block_timestamp: Some(blk.timestamp().to_owned()),
+                        block_number: blk.number,
+                        log_index: log.block_index,
+                        tx_index: origin.trace.index,
+                        tx_from: tx_from.clone(),
+                    });
+
+                    // Update user P&L
+                    update_user_pnl(&mut user_pnls, &from_addr, &token_id, &amount_usdc, blk, false);
+                } else if !is_zero_address(&transfer.from) {
+                    drops.add(diagnostics::EXCLUDED_ADDRESS, 1);
                 }
-            }
-            
-            // Process OrderFilled events for price data
-            if log.address == CTF_EXCHANGE_CONTRACT || log.address == NEG_RISK_CTF_EXCHANGE {
-                if let Some(mut order_fill) = abi::decode_order_filled(log) {
-                    order_fill.evt_tx_hash = tx_hash.clone();
-                    let is_neg_risk = log.address == NEG_RISK_CTF_EXCHANGE;
-                    
-                    // Calculate price from order fill (like subgraph price calculation)
-                    let price = calculate_price_from_order_fill(&order_fill);
-                    
-                    // Update latest prices for each token (like subgraph price feeds)
-                    if order_fill.maker_asset_id != "0" {
-                        latest_prices.insert(order_fill.maker_asset_id.clone(), price);
-                    }
-                    if order_fill.taker_asset_id != "0" {
-                        latest_prices.insert(order_fill.taker_asset_id.clone(), price);
-                    }
-                    
-                    order_fills.push(contract::DuneOrderFill {
-                        transaction_hash: order_fill.evt_tx_hash.clone(),
-                        log_index: order_fill.evt_index,
+
+                // Process receives (positive amount), nobody receives a burn
+                if !is_zero_address(&transfer.to) && !params.is_excluded(&transfer.to) {
+                    token_transfers.push(contract::DuneTokenTransfer {
+                        transaction_hash: transfer.evt_tx_hash.clone(),
+                        user_address: to_addr.clone(),
+                        token_id: token_id.clone(),
+                        amount: amount_usdc.to_string(),
+                        transfer_type: transfer_type.to_string(),
                         block_timestamp: Some(blk.timestamp().to_owned()),
                         block_number: blk.number,
-                        maker_address: Hex(&order_fill.maker).to_string(),
-                        taker_address: Hex(&order_fill.taker).to_string(),
-                        maker_asset_id: order_fill.maker_asset_id.clone(),
-                        taker_asset_id: order_fill.taker_asset_id.clone(),
-                        maker_amount_filled: order_fill.maker_amount_filled.clone(),
-                        taker_amount_filled: order_fill.taker_amount_filled.clone(),
-                        fee: order_fill.fee.clone(),
-                        order_hash: Hex(&order_fill.order_hash).to_string(),
-                        is_neg_risk,
+                        log_index: log.block_index,
+                        tx_index: origin.trace.index,
+                        tx_from: tx_from.clone(),
                     });
 
-                    // Update price data from latest trades (like Dune query prices CTE)
-                    update_price_data(&mut price_data, &order_fill, &blk);
-                    
-                    // Track this transaction hash for USDC filtering
-                    trading_tx_hashes.insert(order_fill.evt_tx_hash.clone());
+                    // Update user P&L
+                    update_user_pnl(&mut user_pnls, &to_addr, &token_id, &amount_usdc, blk, true);
+                } else if !is_zero_address(&transfer.to) {
+                    drops.add(diagnostics::EXCLUDED_ADDRESS, 1);
                 }
-            }
+            },
 
-            // Process Merkle Distributor events for rewards
-            if log.address == UMA_MERKLE_DISTRIBUTOR || log.address == USDC_MERKLE_DISTRIBUTOR {
-                if let Some(claimed) = abi::decode_merkle_claimed(log) {
-                    let asset = if log.address == UMA_MERKLE_DISTRIBUTOR { "uma" } else { "usdc" };
-                    let amount = claimed.amount.clone();
-                    
-                    // Convert amounts based on asset type
-                    let (lc_amount, usd_amount) = if asset == "uma" {
-                        // UMA: divide by 10^18, then multiply by price (simplified to 1 for now)
-                        let lc = if let Ok(val) = amount.parse::<f64>() {
-                            val / 1_000_000_000_000_000_000.0
-                        } else { 0.0 };
-                        (lc.to_string(), lc.to_string()) // Simplified price = 1
-                    } else {
-                        // USDC: divide by 10^6
-                        let usd = if let Ok(val) = amount.parse::<f64>() {
-                            val / 1_000_000.0
-                        } else { 0.0 };
-                        (usd.to_string(), usd.to_string())
-                    };
+            // Process additional USDC airdrops from specific distributor (like Dune query usdc_new CTE)
+            Some(abi::DecodedEvent::Erc20Transfer(mut transfer))
+                if log.address == params.network.usdc && transfer.from == params.network.usdc_merkle_distributor =>
+            {
+                transfer.evt_tx_hash = tx_hash.clone();
+                let to_addr = Hex(&transfer.to).to_string();
+
+                // Convert to USDC units (divide by 1000000) as per Dune query
+                let amount_usdc = raw_to_decimal(&transfer.value, 6).to_string();
 
+                // Add as reward claim, unless the distributor's Claimed already is one
+                if !usdc_claim_tx_hashes.contains(&tx_hash) {
                     reward_claims.push(contract::DuneRewardClaim {
-                        transaction_hash: claimed.evt_tx_hash.clone(),
-                        log_index: claimed.evt_index,
+                        transaction_hash: transfer.evt_tx_hash.clone(),
+                        log_index: log.block_index,
                         block_timestamp: Some(blk.timestamp().to_owned()),
                         block_number: blk.number,
-                        airdrop_recipient: Hex(&claimed.airdrop_recipient).to_string(),
-                        asset: asset.to_string(),
-                        lc_amount,
-                        usd_amount,
-                        token_address: Hex(&log.address).to_string(),
+                        airdrop_recipient: to_addr.clone(),
+                        asset: "usdc".to_string(),
+                        lc_amount: amount_usdc.clone(),
+                        usd_amount: amount_usdc.clone(),
+                        token_address: "0x".to_string(),
+                        tx_index: origin.trace.index,
+                        tx_from: tx_from.clone(),
                     });
                 }
-            }
+
+                // Also add as token transfer
+                token_transfers.push(contract::DuneTokenTransfer {
+                    transaction_hash: transfer.evt_tx_hash.clone(),
+                    user_address: to_addr.clone(),
+                    token_id: "USDC".to_string(),
+                    amount: amount_usdc,
+                    transfer_type: "ERC20_AIRDROP".to_string(),
+                    block_timestamp: Some(blk.timestamp().to_owned()),
+                    block_number: blk.number,
+                    log_index: log.block_index,
+                    tx_index: origin.trace.index,
+                    tx_from: tx_from.clone(),
+                });
+            },
+
+            // Process ERC20 transfers (USDC from Dune query) - ONLY trading-related transfers
+            Some(abi::DecodedEvent::Erc20Transfer(transfer)) if log.address == params.network.usdc => {
+                let from_addr = Hex(&transfer.from).to_string();
+                let to_addr = Hex(&transfer.to).to_string();
+
+                // Only process USDC transfers related to trading (like Dune query erc20 CTE)
+                let is_trading_related = trading_tx_hashes.contains(&tx_hash) ||
+                    transfer.from == params.network.ctf || // CTF Contract
+                    transfer.to == params.network.ctf ||   // CTF Contract
+                    amm_market_addresses.contains(&from_addr) ||  // AMM markets
+                    amm_market_addresses.contains(&to_addr);      // AMM markets
+
+                if is_trading_related {
+                    // Convert to USDC units (divide by 1000000) as per Dune query
+                    let amount_usdc = raw_to_decimal(&transfer.value, 6);
+
+                    // Process USDC transfers
+                    if !params.is_excluded(&transfer.from) {
+                        token_transfers.push(contract::DuneTokenTransfer {
+                            transaction_hash: tx_hash.clone(),
+                            user_address: from_addr.clone(),
+                            token_id: "USDC".to_string(),
+                            amount: amount_usdc.neg().to_string(),
+                            transfer_type: "ERC20".to_string(),
+                            block_timestamp: Some(blk.timestamp().to_owned()),
+                            block_number: blk.number,
+                            log_index: log.block_index,
+                            tx_index: origin.trace.index,
+                            tx_from: tx_from.clone(),
+                        });
+                    } else {
+                        drops.add(diagnostics::EXCLUDED_ADDRESS, 1);
+                    }
+
+                    if !params.is_excluded(&transfer.to) {
+                        token_transfers.push(contract::DuneTokenTransfer {
+                            transaction_hash: tx_hash.clone(),
+                            user_address: to_addr.clone(),
+                            token_id: "USDC".to_string(),
+                            amount: amount_usdc.to_string(),
+                            transfer_type: "ERC20".to_string(),
+                            block_timestamp: Some(blk.timestamp().to_owned()),
+                            block_number: blk.number,
+                            log_index: log.block_index,
+                            tx_index: origin.trace.index,
+                            tx_from: tx_from.clone(),
+                        });
+                    } else {
+                        drops.add(diagnostics::EXCLUDED_ADDRESS, 1);
+                    }
+                } else {
+                    drops.add(diagnostics::USDC_NOT_TRADING_RELATED, 1);
+                }
+            },
+
+            // Process OrderFilled events for price data
+            Some(abi::DecodedEvent::OrderFilled(mut order_fill)) => {
+                order_fill.evt_tx_hash = tx_hash.clone();
+                let is_neg_risk = log.address == params.network.neg_risk_ctf_exchange;
+
+                // Calculate price from order fill (like subgraph price calculation)
+                let price = calculate_price_from_order_fill(&order_fill);
+
+                // The taker order is reported again as a fill against the exchange itself, only maker fills add volume
+                if order_fill.taker != log.address && (params.watches(&order_fill.maker) || params.watches(&order_fill.taker)) {
+                    if let Some(economics) = fill_economics(&order_fill) {
+                        total_volume = total_volume + economics.notional;
+                    }
+                }
+
+                // Update latest prices for each token (like subgraph price feeds)
+                if order_fill.maker_asset_id != "0" {
+                    latest_prices.insert(order_fill.maker_asset_id.clone(), price.clone());
+                }
+                if order_fill.taker_asset_id != "0" {
+                    latest_prices.insert(order_fill.taker_asset_id.clone(), price);
+                }
+
+                order_fills.push(contract::DuneOrderFill {
+                    transaction_hash: order_fill.evt_tx_hash.clone(),
+                    log_index: order_fill.evt_index,
+                    block_timestamp: Some(blk.timestamp().to_owned()),
+                    block_number: blk.number,
+                    maker_address: Hex(&order_fill.maker).to_string(),
+                    taker_address: Hex(&order_fill.taker).to_string(),
+                    maker_asset_id: order_fill.maker_asset_id.clone(),
+                    taker_asset_id: order_fill.taker_asset_id.clone(),
+                    maker_amount_filled: order_fill.maker_amount_filled.clone(),
+                    taker_amount_filled: order_fill.taker_amount_filled.clone(),
+                    fee: order_fill.fee.clone(),
+                    order_hash: Hex(&order_fill.order_hash).to_string(),
+                    is_neg_risk,
+                    tx_index: origin.trace.index,
+                    tx_from: tx_from.clone(),
+                });
+
+                // Update price data from latest trades (like Dune query prices CTE)
+                update_price_data(&mut price_data, &order_fill, blk);
+
+                // Track this transaction hash for USDC filtering
+                trading_tx_hashes.insert(order_fill.evt_tx_hash.clone());
+            },
+
+            // Process Merkle Distributor claims for rewards (like Dune query uma and usdc CTEs)
+            Some(abi::DecodedEvent::MerkleClaimed(claimed)) => {
+                let asset = if log.address == params.network.uma_merkle_distributor { "uma" } else { "usdc" };
+
+                // UMA: divide by 10^18, then multiply by price (simplified to 1 for now), USDC: divide by 10^6
+                let decimals = if asset == "uma" { 18 } else { 6 };
+                let lc_amount = raw_to_decimal(&claimed.amount, decimals).to_string();
+                let usd_amount = lc_amount.clone();
+
+                reward_claims.push(contract::DuneRewardClaim {
+                    transaction_hash: tx_hash.clone(),
+                    log_index: claimed.evt_index,
+                    block_timestamp: Some(blk.timestamp().to_owned()),
+                    block_number: blk.number,
+                    airdrop_recipient: Hex(&claimed.airdrop_recipient).to_string(),
+                    asset: asset.to_string(),
+                    lc_amount,
+                    usd_amount,
+                    token_address: Hex(&log.address).to_string(),
+                    tx_index: origin.trace.index,
+                    tx_from: tx_from.clone(),
+                });
+            },
+
+            _ => {},
         }
     }
 
     // Calculate final P&L for each user (like Dune query trading_pnl and liq_pnl CTEs)
-    for (_, user_pnl) in user_pnls.iter_mut() {
+    for user_pnl in user_pnls.values_mut() {
         calculate_user_pnl(user_pnl, &price_data);
         
         // Update holdings with real prices from subgraph price feeds
         for holding in &mut user_pnl.holdings {
             if let Some(price) = latest_prices.get(&holding.token_id) {
                 holding.latest_price = price.to_string();
-                holding.share_value = (holding.amount.parse::<f64>().unwrap_or(0.0) * price).to_string();
+                holding.share_value = (parse_decimal(&holding.amount) * price.clone()).to_string();
             }
         }
     }
 
+    // Wallet-filtered mode keeps only the events touching the wallets, and the markets and prices of their tokens
+    if params.is_wallet_filtered() {
+        let records = user_pnls.len() + token_transfers.len() + order_fills.len() + reward_claims.len() + market_data.len() + price_data.len();
+        user_pnls.retain(|user_address, _| params.watches_hex(user_address));
+        token_transfers.retain(|transfer| params.watches_hex(&transfer.user_address));
+        order_fills.retain(|fill| params.watches_hex(&fill.maker_address) || params.watches_hex(&fill.taker_address));
+        reward_claims.retain(|claim| params.watches_hex(&claim.airdrop_recipient));
+
+        let tokens: std::collections::HashSet<&String> = token_transfers.iter().map(|transfer| &transfer.token_id)
+            .chain(order_fills.iter().flat_map(|fill| [&fill.maker_asset_id, &fill.taker_asset_id]))
+            .collect();
+        market_data.retain(|_, market| tokens.contains(&market.token0) || tokens.contains(&market.token1));
+        price_data.retain(|token_id, _| tokens.contains(token_id));
+
+        let kept = user_pnls.len() + token_transfers.len() + order_fills.len() + reward_claims.len() + market_data.len() + price_data.len();
+        drops.add(diagnostics::WALLET_FILTER, records - kept);
+    }
+
     // Convert HashMap to Vec
     pnl_data.user_pnls = user_pnls.into_values().collect();
     pnl_data.market_data = market_data.into_values().collect();
@@ -770,65 +686,1325 @@ fn map_pure_dune_pnl(blk: eth::Block) -> Result<contract::PureDunePnL, substream
     pnl_data.total_users = pnl_data.user_pnls.len().to_string();
     
     // Calculate totals (like Dune query final SELECT)
-    let total_volume = 0.0;
-    let mut total_profits = 0.0;
-    let mut total_losses = 0.0;
+    let mut total_profits = BigDecimal::zero();
+    let mut total_losses = BigDecimal::zero();
     
     for user in &pnl_data.user_pnls {
-        let pnl: f64 = user.total_pnl.parse().unwrap_or(0.0);
-        if pnl > 0.0 {
-            total_profits += pnl;
+        let pnl = parse_decimal(&user.total_pnl);
+        if pnl > BigDecimal::zero() {
+            total_profits = total_profits + pnl;
         } else {
-            total_losses += pnl.abs();
+            total_losses = total_losses + pnl.absolute();
         }
-        // Add volume calculation here if needed
     }
     
-    pnl_data.total_volume = total_volume.to_string();
+    pnl_data.total_volume = total_volume.to_decimal(6).to_string();
     pnl_data.total_profits = total_profits.to_string();
     pnl_data.total_losses = total_losses.to_string();
+    pnl_data.dropped_records = drops.records("map_pure_dune_pnl");
 
-    Ok(pnl_data)
+    pnl_data
 }
 
-// Helper function to check if address is excluded (from Dune query)
-fn is_excluded_address(addr: &str) -> bool {
-    EXCLUDED_ADDRESSES.contains(&addr)
+// 14. Market Registry - token and condition lookups built from TokenRegistered on both exchanges
+#[substreams::handlers::store]
+fn store_market_registry(
+    ctf_registrations: contract::TokenRegisteredEvents,
+    neg_risk_registrations: contract::NegRiskTokenRegisteredEvents,
+    store: StoreSetIfNotExistsProto<contract::DuneMarketData>,
+) {
+    register_markets(&ctf_registrations, &neg_risk_registrations, &store);
+}
+
+fn register_markets(
+    ctf_registrations: &contract::TokenRegisteredEvents,
+    neg_risk_registrations: &contract::NegRiskTokenRegisteredEvents,
+    store: &impl StoreSetIfNotExists<contract::DuneMarketData>,
+) {
+    // TokenRegistered is emitted once per token ordering, keep the first registration of each market
+    let registrations = ctf_registrations.token_registered.iter()
+        .map(|evt| (evt.evt_index, &evt.condition_id, &evt.token0, &evt.token1, &evt.evt_block_time, evt.evt_block_number, false))
+        .chain(neg_risk_registrations.neg_risk_token_registered.iter()
+            .map(|evt| (evt.evt_index, &evt.condition_id, &evt.token0, &evt.token1, &evt.evt_block_time, evt.evt_block_number, true)));
+
+    for (evt_index, condition_id, token0, token1, block_time, block_number, is_neg_risk) in registrations {
+        let market = contract::DuneMarketData {
+            condition_id: format!("0x{}", Hex(condition_id)),
+            token0: token0.clone(),
+            token1: token1.clone(),
+            question: String::new(),
+            is_neg_risk,
+            created_at: *block_time,
+            block_number,
+        };
+
+        let ordinal = evt_index as u64;
+        store.set_if_not_exists(ordinal, format!("condition:{}", market.condition_id), &market);
+        store.set_if_not_exists(ordinal, format!("token:{}", token0), &market);
+        store.set_if_not_exists(ordinal, format!("token:{}", token1), &market);
+    }
 }
 
-// Helper function to check if address is an AMM market (from Dune query amm_markets CTE)
-fn is_amm_market_address(_addr: &str) -> bool {
-    // In a real implementation, you'd maintain a list of AMM market addresses
-    // from FPMM Factory creation events. For now, return false.
-    // This should be populated from map_fpmm_factory_creation events.
-    false
+// 15. Condition State - latest ConditionPreparation/ConditionResolution per condition (payouts empty until resolved)
+#[substreams::handlers::store]
+fn store_conditions(ctf_events: contract::CtfEvents, store: StoreSetProto<contract::CtfConditionResolution>) {
+    set_conditions(&ctf_events, &store);
 }
 
-// Helper function to get question text from condition_id
-// This would ideally connect to the same metadata source as Dune query
-fn get_question_from_condition_id(condition_id: &str) -> String {
-    // For now, return a placeholder. In production, this should:
-    // 1. Query the same metadata source as Dune query
-    // 2. Or decode from blockchain events if available
-    // 3. Or maintain a local cache of condition_id -> question mappings
-    
-    // Placeholder implementation - in real implementation, you'd:
-    // - Query dune.lifewillbeokay.dataset_polymarket_metadata
-    // - Or maintain a local database/cache
-    // - Or decode from CTF events if question is stored on-chain
-    
-    format!("Market for condition {}", condition_id)
+fn set_conditions(ctf_events: &contract::CtfEvents, store: &impl StoreSet<contract::CtfConditionResolution>) {
+    for preparation in &ctf_events.condition_preparations {
+        store.set(
+            preparation.evt_index as u64,
+            format!("condition:0x{}", Hex(&preparation.condition_id)),
+            &contract::CtfConditionResolution {
+                evt_tx_hash: preparation.evt_tx_hash.clone(),
+                evt_index: preparation.evt_index,
+                evt_block_time: preparation.evt_block_time,
+                evt_block_number: preparation.evt_block_number,
+                evt_tx_index: preparation.evt_tx_index,
+                evt_tx_from: preparation.evt_tx_from.clone(),
+                condition_id: preparation.condition_id.clone(),
+                oracle: preparation.oracle.clone(),
+                question_id: preparation.question_id.clone(),
+                outcome_slot_count: preparation.outcome_slot_count,
+                payout_numerators: Vec::new(),
+                payout_denominator: String::new(),
+            },
+        );
+    }
+
+    for resolution in &ctf_events.condition_resolutions {
+        store.set(
+            resolution.evt_index as u64,
+            format!("condition:0x{}", Hex(&resolution.condition_id)),
+            resolution,
+        );
+    }
+}
+
+// 16. Market Volume - per-condition volume, trade count and fees accumulated from OrderFilled on both exchanges
+#[substreams::handlers::store]
+fn store_market_volume(
+    params: String,
+    ctf_fills: contract::OrderFilledEvents,
+    neg_risk_fills: contract::OrderFilledEvents,
+    registry: StoreGetProto<contract::DuneMarketData>,
+    store: StoreAddBigInt,
+) {
+    fail_on_error(add_market_volume(&params, &ctf_fills, &neg_risk_fills, &registry, &store));
+}
+
+fn add_market_volume(
+    params: &str,
+    ctf_fills: &contract::OrderFilledEvents,
+    neg_risk_fills: &contract::OrderFilledEvents,
+    registry: &impl StoreGet<contract::DuneMarketData>,
+    store: &impl StoreAdd<BigInt>,
+) -> Result<(), substreams::errors::Error> {
+    let network = params::Params::parse(params)?.network;
+    for (fill, exchange) in exchange_fills(network, ctf_fills, neg_risk_fills) {
+        let Some(economics) = fill_economics(fill) else { continue };
+        let Some(market) = registry.get_last(format!("token:{}", economics.token_id)) else { continue };
+
+        let ordinal = fill.evt_index as u64;
+        store.add(ordinal, format!("fees:{}", market.condition_id), economics.fee_usdc);
+
+        // The taker order is reported again as a fill against the exchange itself, count only maker fills
+        if fill.taker != exchange {
+            store.add(ordinal, format!("volume:{}", market.condition_id), economics.notional);
+            store.add(ordinal, format!("trades:{}", market.condition_id), BigInt::one());
+        }
+    }
+    Ok(())
+}
+
+// 17. Market P&L - emits the OnChainMarketPnL row of every market touched in this block
+#[substreams::handlers::map]
+fn map_market_pnl(
+    params: String,
+    blk: eth::Block,
+    ctf_fills: contract::OrderFilledEvents,
+    neg_risk_fills: contract::OrderFilledEvents,
+    ctf_registrations: contract::TokenRegisteredEvents,
+    neg_risk_registrations: contract::NegRiskTokenRegisteredEvents,
+    ctf_events: contract::CtfEvents,
+    registry: StoreGetProto<contract::DuneMarketData>,
+    conditions: StoreGetProto<contract::CtfConditionResolution>,
+    volume: StoreGetBigInt,
+) -> Result<contract::OnChainMarketPnLs, substreams::errors::Error> {
+    let network = params::Params::parse(&params)?.network;
+    let registrations = (&ctf_registrations, &neg_risk_registrations);
+    Ok(market_pnls(network, &blk, (&ctf_fills, &neg_risk_fills), registrations, &ctf_events, &registry, &conditions, &volume))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn market_pnls(
+    network: &network::NetworkProfile,
+    blk: &eth::Block,
+    (ctf_fills, neg_risk_fills): (&contract::OrderFilledEvents, &contract::OrderFilledEvents),
+    (ctf_registrations, neg_risk_registrations): (&contract::TokenRegisteredEvents, &contract::NegRiskTokenRegisteredEvents),
+    ctf_events: &contract::CtfEvents,
+    registry: &impl StoreGet<contract::DuneMarketData>,
+    conditions: &impl StoreGet<contract::CtfConditionResolution>,
+    volume: &impl StoreGet<BigInt>,
+) -> contract::OnChainMarketPnLs {
+    let mut changed_conditions: BTreeSet<String> = BTreeSet::new();
+
+    for (fill, _) in exchange_fills(network, ctf_fills, neg_risk_fills) {
+        if let Some(market) = fill_economics(fill)
+            .and_then(|economics| registry.get_last(format!("token:{}", economics.token_id)))
+        {
+            changed_conditions.insert(market.condition_id);
+        }
+    }
+    for registration in &ctf_registrations.token_registered {
+        changed_conditions.insert(format!("0x{}", Hex(&registration.condition_id)));
+    }
+    for registration in &neg_risk_registrations.neg_risk_token_registered {
+        changed_conditions.insert(format!("0x{}", Hex(&registration.condition_id)));
+    }
+    for preparation in &ctf_events.condition_preparations {
+        changed_conditions.insert(format!("0x{}", Hex(&preparation.condition_id)));
+    }
+    for resolution in &ctf_events.condition_resolutions {
+        changed_conditions.insert(format!("0x{}", Hex(&resolution.condition_id)));
+    }
+
+    let market_pnls = changed_conditions.iter()
+        .map(|condition_id| {
+            let market = registry.get_last(format!("condition:{}", condition_id));
+            let condition = conditions.get_last(format!("condition:{}", condition_id));
+            build_market_pnl(condition_id, market.as_ref(), condition.as_ref(), volume, blk.number)
+        })
+        .collect();
+
+    contract::OnChainMarketPnLs { market_pnls }
+}
+
+// 18. Global First-Seen Markers - user:, market: and per-day keys are created the first time they appear
+#[substreams::handlers::store]
+fn store_global_first_seen(
+    params: String,
+    clock: Clock,
+    ctf_fills: contract::OrderFilledEvents,
+    neg_risk_fills: contract::OrderFilledEvents,
+    ctf_registrations: contract::TokenRegisteredEvents,
+    neg_risk_registrations: contract::NegRiskTokenRegisteredEvents,
+    registry: StoreGetProto<contract::DuneMarketData>,
+    store: StoreSetIfNotExistsInt64,
+) {
+    let registrations = (&ctf_registrations, &neg_risk_registrations);
+    fail_on_error(set_global_first_seen(&params, utc_day(&clock), (&ctf_fills, &neg_risk_fills), registrations, &registry, &store));
+}
+
+fn set_global_first_seen(
+    params: &str,
+    day: u64,
+    (ctf_fills, neg_risk_fills): (&contract::OrderFilledEvents, &contract::OrderFilledEvents),
+    (ctf_registrations, neg_risk_registrations): (&contract::TokenRegisteredEvents, &contract::NegRiskTokenRegisteredEvents),
+    registry: &impl StoreGet<contract::DuneMarketData>,
+    store: &impl StoreSetIfNotExists<i64>,
+) -> Result<(), substreams::errors::Error> {
+    let network = params::Params::parse(params)?.network;
+    for (fill, exchange) in exchange_fills(network, ctf_fills, neg_risk_fills) {
+        // Maker fills name both counterparties, the taker order fill only repeats the taker
+        if fill.taker == exchange {
+            continue;
+        }
+
+        let ordinal = fill.evt_index as u64;
+        for trader in [&fill.maker, &fill.taker] {
+            let trader = format!("0x{}", Hex(trader));
+            store.set_if_not_exists(ordinal, format!("user:{}", trader), &1);
+            store.set_if_not_exists(ordinal, format!("day:{}:user:{}", day, trader), &1);
+        }
+
+        if let Some(market) = fill_economics(fill)
+            .and_then(|economics| registry.get_last(format!("token:{}", economics.token_id)))
+        {
+            store.set_if_not_exists(ordinal, format!("day:{}:market:{}", day, market.condition_id), &1);
+        }
+    }
+
+    for registration in &ctf_registrations.token_registered {
+        store.set_if_not_exists(registration.evt_index as u64, format!("market:0x{}", Hex(&registration.condition_id)), &1);
+    }
+    for registration in &neg_risk_registrations.neg_risk_token_registered {
+        store.set_if_not_exists(registration.evt_index as u64, format!("market:0x{}", Hex(&registration.condition_id)), &1);
+    }
+    Ok(())
+}
+
+// 19. Global Statistics - cumulative counters and the same counters under day:<day>: (USDC base units)
+#[substreams::handlers::store]
+fn store_global_stats(
+    params: String,
+    clock: Clock,
+    ctf_fills: contract::OrderFilledEvents,
+    neg_risk_fills: contract::OrderFilledEvents,
+    ctf_events: contract::CtfEvents,
+    first_seen: Deltas<DeltaInt64>,
+    registry: StoreGetProto<contract::DuneMarketData>,
+    store: StoreAddBigInt,
+) {
+    fail_on_error(add_global_stats(&params, utc_day(&clock), (&ctf_fills, &neg_risk_fills), &ctf_events, &first_seen, &registry, &store));
+}
+
+fn add_global_stats(
+    params: &str,
+    day: u64,
+    (ctf_fills, neg_risk_fills): (&contract::OrderFilledEvents, &contract::OrderFilledEvents),
+    ctf_events: &contract::CtfEvents,
+    first_seen: &Deltas<DeltaInt64>,
+    registry: &impl StoreGet<contract::DuneMarketData>,
+    store: &impl StoreAdd<BigInt>,
+) -> Result<(), substreams::errors::Error> {
+    let network = params::Params::parse(params)?.network;
+    for (fill, exchange) in exchange_fills(network, ctf_fills, neg_risk_fills) {
+        let Some(economics) = fill_economics(fill) else { continue };
+
+        let ordinal = fill.evt_index as u64;
+        store.add_many(ordinal, &vec!["fees".to_string(), format!("day:{}:fees", day)], economics.fee_usdc);
+
+        // The taker order is reported again as a fill against the exchange itself, count only maker fills
+        if fill.taker != exchange {
+            store.add_many(ordinal, &vec!["volume".to_string(), format!("day:{}:volume", day)], economics.notional);
+            store.add_many(ordinal, &vec!["trades".to_string(), format!("day:{}:trades", day)], BigInt::one());
+        }
+    }
+
+    for delta in first_seen.deltas.iter().filter(|delta| delta.operation == Operation::Create) {
+        let segments: Vec<&str> = delta.key.split(':').collect();
+        let counter = match segments.as_slice() {
+            ["user", _] => "users".to_string(),
+            ["market", _] => "markets".to_string(),
+            ["day", day, "user", _] => format!("day:{}:users", day),
+            ["day", day, "market", _] => format!("day:{}:markets", day),
+            _ => continue,
+        };
+        store.add(delta.ordinal, counter, BigInt::one());
+    }
+
+    for resolution in &ctf_events.condition_resolutions {
+        // Only count conditions that are registered exchange markets
+        if registry.has_last(format!("condition:0x{}", Hex(&resolution.condition_id))) {
+            store.add_many(
+                resolution.evt_index as u64,
+                &vec!["resolved_markets".to_string(), format!("day:{}:resolved_markets", day)],
+                BigInt::one(),
+            );
+        }
+    }
+    Ok(())
+}
+
+// 20. Global Day - current UTC day number, its deltas mark day boundaries
+#[substreams::handlers::store]
+fn store_global_day(clock: Clock, store: StoreSetInt64) {
+    set_global_day(&clock, &store);
+}
+
+fn set_global_day(clock: &Clock, store: &impl StoreSet<i64>) {
+    store.set(0, "day", &(utc_day(clock) as i64));
+}
+
+// 21. Global P&L - a RUNNING snapshot every block, plus a DAY snapshot when a UTC day closes
+#[substreams::handlers::map]
+fn map_global_pnl(
+    clock: Clock,
+    day_deltas: Deltas<DeltaInt64>,
+    stats: StoreGetBigInt,
+) -> Result<contract::OnChainGlobalPnLs, substreams::errors::Error> {
+    Ok(global_pnls(&clock, &day_deltas, &stats))
+}
+
+fn global_pnls(clock: &Clock, day_deltas: &Deltas<DeltaInt64>, stats: &impl StoreGet<BigInt>) -> contract::OnChainGlobalPnLs {
+    let mut global_pnls = Vec::new();
+
+    for delta in day_deltas.deltas.iter() {
+        if delta.key == "day" && delta.old_value != 0 && delta.old_value != delta.new_value {
+            let day_start = prost_types::Timestamp { seconds: delta.old_value * SECONDS_PER_DAY as i64, nanos: 0 };
+            global_pnls.push(build_global_pnl(stats, &format!("day:{}:", delta.old_value), delta.old_value as u64, "DAY", day_start, clock.number));
+        }
+    }
+
+    global_pnls.push(build_global_pnl(stats, "", utc_day(clock), "RUNNING", clock.timestamp.unwrap_or_default(), clock.number));
+
+    contract::OnChainGlobalPnLs { global_pnls }
+}
+
+// 22. FPMM Markets - FixedProductMarketMakerCreation keyed by market contract address
+#[substreams::handlers::store]
+fn store_fpmm_markets(creations: contract::FpmmFactoryEvents, store: StoreSetProto<contract::FpmmFactoryCreation>) {
+    set_fpmm_markets(&creations, &store);
+}
+
+fn set_fpmm_markets(creations: &contract::FpmmFactoryEvents, store: &impl StoreSet<contract::FpmmFactoryCreation>) {
+    for creation in &creations.factory_creations {
+        store.set(
+            creation.evt_index as u64,
+            format!("fpmm:0x{}", Hex(&creation.fixed_product_market_maker)),
+            creation,
+        );
+    }
+}
+
+// 23. Enriched Trades - CLOB, NegRisk and FPMM fills with sides, outcome token, price and USDC amounts
+#[substreams::handlers::map]
+fn map_trades(
+    params: String,
+    blk: eth::Block,
+    registry: StoreGetProto<contract::DuneMarketData>,
+    conditions: StoreGetProto<contract::CtfConditionResolution>,
+    fpmm_markets: StoreGetProto<contract::FpmmFactoryCreation>,
+    collaterals: StoreGetProto<contract::CollateralToken>,
+    position_collaterals: StoreGetProto<contract::CollateralToken>,
+) -> Result<contract::Trades, substreams::errors::Error> {
+    let network = params::Params::parse(&params)?.network;
+    Ok(trades(network, &blk, &registry, &conditions, &fpmm_markets, &collaterals, &position_collaterals))
 }
 
-fn calculate_price_from_order_fill(order_fill: &contract::OrderFilled) -> f64 {
+fn trades(
+    network: &network::NetworkProfile,
+    blk: &eth::Block,
+    registry: &impl StoreGet<contract::DuneMarketData>,
+    conditions: &impl StoreGet<contract::CtfConditionResolution>,
+    fpmm_markets: &impl StoreGet<contract::FpmmFactoryCreation>,
+    collaterals: &impl StoreGet<contract::CollateralToken>,
+    position_collaterals: &impl StoreGet<contract::CollateralToken>,
+) -> contract::Trades {
+    let mut trades = contract::Trades::default();
+
+    for origin in events::block_logs(blk) {
+        let log = origin.log;
+        let trade = match abi::route(network, log) {
+            Some(abi::DecodedEvent::OrderFilled(fill)) => build_clob_trade(&fill, network, &log.address, registry, conditions, position_collaterals),
+            Some(abi::DecodedEvent::FpmmTrade(fpmm_trade)) => {
+                // Trades in an unknown collateral are left out rather than scaled by a guess
+                fpmm_markets.get_last(format!("fpmm:0x{}", Hex(&log.address))).and_then(|market| {
+                    let decimals = collateral::decimals(collaterals, &market.collateral_token)?;
+                    Some(build_fpmm_trade(&fpmm_trade, log, &market, decimals, conditions))
+                })
+            },
+            _ => None,
+        };
+
+        if let Some(mut trade) = trade {
+            trade.set_origin(&origin, blk);
+            trades.trades.push(trade);
+        }
+    }
+
+    trades
+}
+
+// 24. Order Matches - OrdersMatched linked to the OrderFilled logs emitted before it in the same tx
+#[substreams::handlers::map]
+fn map_order_matches(
+    params: String,
+    blk: eth::Block,
+    registry: StoreGetProto<contract::DuneMarketData>,
+) -> Result<contract::OrderMatches, substreams::errors::Error> {
+    Ok(order_matches(params::Params::parse(&params)?.network, &blk, &registry))
+}
+
+fn order_matches(network: &network::NetworkProfile, blk: &eth::Block, registry: &impl StoreGet<contract::DuneMarketData>) -> contract::OrderMatches {
+    let mut matches = contract::OrderMatches::default();
+
+    // OrderFilled logs not yet claimed by an OrdersMatched, per transaction and exchange
+    let mut pending_fills: HashMap<(u32, Vec<u8>), Vec<contract::OrderFilled>> = HashMap::new();
+
+    for origin in events::block_logs(blk) {
+        let log = origin.log;
+        let pending_key = (origin.trace.index, log.address.clone());
+        match abi::route(network, log) {
+            Some(abi::DecodedEvent::OrderFilled(mut fill)) => {
+                fill.set_origin(&origin, blk);
+                pending_fills.entry(pending_key).or_default().push(fill);
+            },
+            Some(abi::DecodedEvent::OrdersMatched(mut orders_matched)) => {
+                orders_matched.set_origin(&origin, blk);
+                let fills = pending_fills.remove(&pending_key).unwrap_or_default();
+                matches.matches.push(build_order_match(orders_matched, fills, network, &log.address, registry));
+            },
+            _ => {},
+        }
+    }
+
+    matches
+}
+
+// 25. Exchange Admin and Order Lifecycle Events (OrderCancelled, FeeCharged, pauses, role and factory changes)
+#[substreams::handlers::map]
+fn map_exchange_events(params: String, blk: eth::Block) -> Result<contract::ExchangeEvents, substreams::errors::Error> {
+    Ok(exchange_events(params::Params::parse(&params)?.network, &blk))
+}
+
+fn exchange_events(network: &network::NetworkProfile, blk: &eth::Block) -> contract::ExchangeEvents {
+    let mut events = contract::ExchangeEvents::default();
+
+    for origin in events::block_logs(blk) {
+        let log = origin.log;
+        let Some(abi::DecodedEvent::Exchange(event)) = abi::route(network, log) else {
+            continue;
+        };
+
+        match event {
+            abi::ExchangeEventType::OrderCancelled(mut evt) => {
+                evt.set_origin(&origin, blk);
+                events.order_cancellations.push(evt);
+            },
+            abi::ExchangeEventType::FeeCharged(mut evt) => {
+                evt.set_origin(&origin, blk);
+                events.fees_charged.push(evt);
+            },
+            abi::ExchangeEventType::TradingPaused(mut evt) => {
+                evt.set_origin(&origin, blk);
+                events.trading_pauses.push(evt);
+            },
+            abi::ExchangeEventType::RoleChange(mut evt) => {
+                evt.set_origin(&origin, blk);
+                events.role_changes.push(evt);
+            },
+            abi::ExchangeEventType::FactoryUpdate(mut evt) => {
+                evt.set_origin(&origin, blk);
+                events.factory_updates.push(evt);
+            },
+        }
+    }
+
+    events
+}
+
+// 26. Positions - outcome token balance per <wallet>:<token_id> from CTF ERC1155 transfers (raw units)
+#[substreams::handlers::store]
+fn store_positions(params: String, ctf_events: contract::CtfEvents, store: StoreAddBigInt) {
+    fail_on_error(add_positions(&params, &ctf_events, &store));
+}
+
+fn add_positions(params: &str, ctf_events: &contract::CtfEvents, store: &impl StoreAdd<BigInt>) -> Result<(), substreams::errors::Error> {
+    let params = &params::Params::parse(params)?;
+    for (ordinal, key, value) in position_deltas(params, ctf_events) {
+        store.add(ordinal, key, value);
+    }
+    Ok(())
+}
+
+// 27. Wallet Cash - net USDC per wallet from order fills, splits, merges and redemptions (raw units)
+#[substreams::handlers::store]
+fn store_wallet_cash(
+    params: String,
+    ctf_fills: contract::OrderFilledEvents,
+    neg_risk_fills: contract::OrderFilledEvents,
+    ctf_events: contract::CtfEvents,
+    collaterals: StoreGetProto<contract::CollateralToken>,
+    store: StoreAddBigInt,
+) {
+    fail_on_error(add_wallet_cash_flows(&params, (&ctf_fills, &neg_risk_fills), &ctf_events, &collaterals, &store));
+}
+
+fn add_wallet_cash_flows(
+    params: &str,
+    fills: (&contract::OrderFilledEvents, &contract::OrderFilledEvents),
+    ctf_events: &contract::CtfEvents,
+    collaterals: &impl StoreGet<contract::CollateralToken>,
+    store: &impl StoreAdd<BigInt>,
+) -> Result<(), substreams::errors::Error> {
+    let params = params::Params::parse(params)?;
+    for (ordinal, wallet, value) in wallet_cash_flows(&params, fills, ctf_events, collaterals) {
+        store.add(ordinal, wallet, value);
+    }
+    Ok(())
+}
+
+// store_wallet_cash deltas as (ordinal, <wallet>, USDC base units), paid out negative and received positive
+fn wallet_cash_flows(
+    params: &params::Params,
+    (ctf_fills, neg_risk_fills): (&contract::OrderFilledEvents, &contract::OrderFilledEvents),
+    ctf_events: &contract::CtfEvents,
+    collaterals: &impl StoreGet<contract::CollateralToken>,
+) -> Vec<(u64, String, BigInt)> {
+    let mut flows = Vec::new();
+    let mut add_cash = |ordinal: u32, address: &[u8], value: BigInt| {
+        if let Some(wallet) = tracked_wallet(params, address) {
+            flows.push((ordinal as u64, wallet, value));
+        }
+    };
+
+    // Every order owner is the maker of exactly one fill per order, the taker order included
+    for (fill, _) in exchange_fills(params.network, ctf_fills, neg_risk_fills) {
+        if let Some(economics) = fill_economics(fill) {
+            let cash = if economics.maker_is_buyer {
+                economics.notional.neg()
+            } else {
+                economics.notional - economics.fee_usdc
+            };
+            add_cash(fill.evt_index, &fill.maker, cash);
+        }
+    }
+
+    // Splits, merges and redemptions are in their own collateral, rescaled to USDC base units. Those in an unknown
+    // collateral are not counted
+    for split in &ctf_events.position_splits {
+        if let Some(amount) = collateral_amount(collaterals, &split.collateral_token, &split.amount) {
+            add_cash(split.evt_index, &split.stakeholder, amount.neg());
+        }
+    }
+    for merge in &ctf_events.position_merges {
+        if let Some(amount) = collateral_amount(collaterals, &merge.collateral_token, &merge.amount) {
+            add_cash(merge.evt_index, &merge.stakeholder, amount);
+        }
+    }
+    for redemption in &ctf_events.position_redemptions {
+        if let Some(payout) = collateral_amount(collaterals, &redemption.collateral_token, &redemption.payout) {
+            add_cash(redemption.evt_index, &redemption.redeemer, payout);
+        }
+    }
+
+    flows
+}
+
+// 28. Database Output - markets, trades, transfers, reward claims, positions and wallet P&L for substreams-sink-sql
+#[substreams::handlers::map]
+#[allow(clippy::too_many_arguments)]
+fn db_out(
+    clock: Clock,
+    market_pnls: contract::OnChainMarketPnLs,
+    trades: contract::Trades,
+    ctf_events: contract::CtfEvents,
+    uma_claims: contract::MerkleDistributorEvents,
+    usdc_claims: contract::MerkleDistributorEvents,
+    positions: Deltas<DeltaBigInt>,
+    wallet_cash: Deltas<DeltaBigInt>,
+    collaterals: Deltas<DeltaProto<contract::CollateralToken>>,
+    position_collaterals: StoreGetProto<contract::CollateralToken>,
+) -> Result<DatabaseChanges, substreams::errors::Error> {
+    let mut tables = Tables::new();
+
+    db::market_rows(&mut tables, &market_pnls);
+    db::trade_rows(&mut tables, &trades);
+    db::token_price_rows(&mut tables, &trades);
+    db::transfer_rows(&mut tables, &ctf_events, &position_collaterals);
+    db::reward_claim_rows(&mut tables, &uma_claims, "uma", 18);
+    db::reward_claim_rows(&mut tables, &usdc_claims, "usdc", 6);
+    db::position_rows(&mut tables, &positions, &position_collaterals, &clock);
+    db::wallet_pnl_rows(&mut tables, &wallet_cash, &clock);
+    db::collateral_rows(&mut tables, &collaterals);
+
+    Ok(tables.to_database_changes())
+}
+
+// 29. Graph Output - Account, Market, Position, Trade, Redemption and RewardClaim entities for a Substreams-powered subgraph
+#[substreams::handlers::map]
+#[allow(clippy::too_many_arguments)]
+fn graph_out(
+    clock: Clock,
+    market_pnls: contract::OnChainMarketPnLs,
+    trades: contract::Trades,
+    ctf_events: contract::CtfEvents,
+    uma_claims: contract::MerkleDistributorEvents,
+    usdc_claims: contract::MerkleDistributorEvents,
+    positions: Deltas<DeltaBigInt>,
+    wallet_cash: Deltas<DeltaBigInt>,
+    collaterals: StoreGetProto<contract::CollateralToken>,
+    position_collaterals: StoreGetProto<contract::CollateralToken>,
+) -> Result<EntityChanges, substreams::errors::Error> {
+    let mut tables = substreams_entity_change::tables::Tables::new();
+
+    graph::market_entities(&mut tables, &market_pnls);
+    graph::trade_entities(&mut tables, &trades);
+    graph::redemption_entities(&mut tables, &ctf_events, &collaterals);
+    graph::reward_claim_entities(&mut tables, &uma_claims, "uma", 18);
+    graph::reward_claim_entities(&mut tables, &usdc_claims, "usdc", 6);
+    graph::position_entities(&mut tables, &positions, &position_collaterals, &clock);
+    graph::account_entities(&mut tables, &wallet_cash, &clock);
+
+    Ok(tables.to_entity_changes())
+}
+
+// 30. KV Output - wallet:<addr>, position:<addr>:<token> and market:<condition> latest records for substreams-sink-kv
+#[substreams::handlers::map]
+fn kv_out(
+    clock: Clock,
+    market_pnls: contract::OnChainMarketPnLs,
+    wallet_pnls: contract::WalletPnLs,
+    positions: Deltas<DeltaBigInt>,
+    position_collaterals: StoreGetProto<contract::CollateralToken>,
+) -> Result<pb::sf::substreams::sink::kv::v1::KvOperations, substreams::errors::Error> {
+    let mut operations = pb::sf::substreams::sink::kv::v1::KvOperations::default();
+
+    kv::market_operations(&mut operations, &market_pnls);
+    kv::position_operations(&mut operations, &positions, &position_collaterals, &clock);
+    kv::wallet_operations(&mut operations, &wallet_pnls);
+
+    Ok(operations)
+}
+
+// 31. Wallet Activity Index - "wallet_activity" for blocks with Polymarket logs touching the params wallets (any wallet when unset)
+#[substreams::handlers::map]
+fn index_wallet_activity(params: String, blk: eth::Block) -> Result<substreams::pb::sf::substreams::index::v1::Keys, substreams::errors::Error> {
+    let params = params::Params::parse(&params)?;
+    let mut keys = substreams::pb::sf::substreams::index::v1::Keys::default();
+
+    if has_wallet_activity(&params, &blk) {
+        keys.keys.push("wallet_activity".to_string());
+    }
+
+    Ok(keys)
+}
+
+fn has_wallet_activity(params: &params::Params, blk: &eth::Block) -> bool {
+    events::block_logs(blk).any(|origin| touches_watched_wallet(params, origin.log))
+}
+
+// 32. Collateral Tokens - symbol and decimals of every collateral seen in CTF splits and FPMM creations
+#[substreams::handlers::store]
+fn store_collateral_tokens(
+    params: String,
+    ctf_events: contract::CtfEvents,
+    creations: contract::FpmmFactoryEvents,
+    store: StoreSetIfNotExistsProto<contract::CollateralToken>,
+) {
+    fail_on_error(set_collateral_tokens(&params, &ctf_events, &creations, &store));
+}
+
+fn set_collateral_tokens(
+    params: &str,
+    ctf_events: &contract::CtfEvents,
+    creations: &contract::FpmmFactoryEvents,
+    store: &impl StoreSetIfNotExists<contract::CollateralToken>,
+) -> Result<(), substreams::errors::Error> {
+    let network = params::Params::parse(params)?.network;
+    let collaterals = ctf_events.position_splits.iter()
+        .map(|split| (split.evt_index, split.evt_block_number, &split.collateral_token))
+        .chain(creations.factory_creations.iter().map(|creation| (creation.evt_index, creation.evt_block_number, &creation.collateral_token)));
+
+    // Resolve each token once per block, at its first use
+    let mut seen: BTreeSet<&Vec<u8>> = BTreeSet::new();
+    for (ordinal, block_number, address) in collaterals {
+        if seen.insert(address) {
+            store.set_if_not_exists(ordinal as u64, collateral::collateral_key(address), &collateral::resolve(network, address, block_number));
+        }
+    }
+    Ok(())
+}
+
+// 33. Pure Dune P&L v2 - map_pure_dune_pnl as contract.v2: bytes addresses, enums and raw integer amounts
+#[substreams::handlers::map]
+fn map_pure_dune_pnl_v2(pnl: contract::PureDunePnL) -> Result<pb::contract::v2::PureDunePnL, substreams::errors::Error> {
+    Ok(v2::pure_dune_pnl(pnl))
+}
+
+// 34. Enriched Trades v2 - map_trades as contract.v2, amounts in collateral base units
+#[substreams::handlers::map]
+fn map_trades_v2(trades: contract::Trades) -> Result<pb::contract::v2::Trades, substreams::errors::Error> {
+    Ok(v2::trades(trades))
+}
+
+// 35. Diagnostics - per-block decode counts per event type, unknown topic0s at watched contracts and records dropped by map_pure_dune_pnl
+#[substreams::handlers::map]
+fn map_diagnostics(params: String, blk: eth::Block, pnl: contract::PureDunePnL) -> Result<contract::BlockDiagnostics, substreams::errors::Error> {
+    let params = params::Params::parse(&params)?;
+    Ok(diagnostics::block_diagnostics(params.network, &blk, pnl.dropped_records))
+}
+
+// 36. Position Collaterals - collateral of every outcome token minted by a CTF split, keyed position:<token_id>
+#[substreams::handlers::store]
+fn store_position_collaterals(
+    ctf_events: contract::CtfEvents,
+    collaterals: StoreGetProto<contract::CollateralToken>,
+    store: StoreSetIfNotExistsProto<contract::CollateralToken>,
+) {
+    set_position_collaterals(&ctf_events, &collaterals, &store);
+}
+
+fn set_position_collaterals(
+    ctf_events: &contract::CtfEvents,
+    collaterals: &impl StoreGet<contract::CollateralToken>,
+    store: &impl StoreSetIfNotExists<contract::CollateralToken>,
+) {
+    for (ordinal, token_id, collateral_token) in position_collaterals(ctf_events) {
+        if let Some(collateral) = collaterals.get_last(collateral::collateral_key(collateral_token)) {
+            store.set_if_not_exists(ordinal, collateral::position_key(token_id), &collateral);
+        }
+    }
+}
+
+// 37. Token Prices - latest CLOB trade price of each outcome token, keyed price:<token_id>
+#[substreams::handlers::store]
+fn store_token_prices(trades: contract::Trades, store: StoreSetBigDecimal) {
+    set_token_prices(&trades, &store);
+}
+
+// FPMM trades carry no token id and are skipped, as in the token_prices table
+fn set_token_prices(trades: &contract::Trades, store: &impl StoreSet<BigDecimal>) {
+    for trade in trades.trades.iter().filter(|trade| !trade.token_id.is_empty()) {
+        store.set(trade.evt_index as u64, price_key(&trade.token_id), &parse_decimal(&trade.price));
+    }
+}
+
+// 38. Wallet Tokens - outcome tokens each wallet has held, appended to tokens:<wallet> when its position is created
+#[substreams::handlers::store]
+fn store_wallet_tokens(positions: Deltas<DeltaBigInt>, store: StoreAppend<String>) {
+    append_wallet_tokens(&positions, &store);
+}
+
+fn append_wallet_tokens(positions: &Deltas<DeltaBigInt>, store: &impl Appender<String>) {
+    for delta in positions.deltas.iter().filter(|delta| delta.operation == Operation::Create) {
+        if let Some((wallet, token_id)) = delta.key.split_once(':') {
+            store.append(delta.ordinal, format!("tokens:{}", wallet), token_id.to_string());
+        }
+    }
+}
+
+// 39. Wallet P&L - net USDC plus held outcome tokens at their latest price, for every wallet whose cash or positions changed
+#[substreams::handlers::map]
+#[allow(clippy::too_many_arguments)]
+fn map_wallet_pnl(
+    params: String,
+    clock: Clock,
+    ctf_fills: contract::OrderFilledEvents,
+    neg_risk_fills: contract::OrderFilledEvents,
+    ctf_events: contract::CtfEvents,
+    collaterals: StoreGetProto<contract::CollateralToken>,
+    positions: StoreGetBigInt,
+    wallet_cash: StoreGetBigInt,
+    wallet_tokens: StoreGetArray<String>,
+    prices: StoreGetBigDecimal,
+    position_collaterals: StoreGetProto<contract::CollateralToken>,
+) -> Result<contract::WalletPnLs, substreams::errors::Error> {
+    let params = params::Params::parse(&params)?;
+    let fills = (&ctf_fills, &neg_risk_fills);
+    Ok(wallet_pnls(&params, &clock, fills, &ctf_events, &collaterals, &positions, &wallet_cash, &wallet_tokens, &prices, &position_collaterals))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn wallet_pnls(
+    params: &params::Params,
+    clock: &Clock,
+    fills: (&contract::OrderFilledEvents, &contract::OrderFilledEvents),
+    ctf_events: &contract::CtfEvents,
+    collaterals: &impl StoreGet<contract::CollateralToken>,
+    positions: &impl StoreGet<BigInt>,
+    wallet_cash: &impl StoreGet<BigInt>,
+    wallet_tokens: &impl StoreGet<Vec<String>>,
+    prices: &impl StoreGet<BigDecimal>,
+    position_collaterals: &impl StoreGet<contract::CollateralToken>,
+) -> contract::WalletPnLs {
+    // The wallets store_positions and store_wallet_cash wrote this block
+    let wallets: BTreeSet<String> = position_deltas(params, ctf_events).into_iter()
+        .filter_map(|(_, key, _)| key.split_once(':').map(|(wallet, _)| wallet.to_string()))
+        .chain(wallet_cash_flows(params, fills, ctf_events, collaterals).into_iter().map(|(_, wallet, _)| wallet))
+        .collect();
+
+    let wallet_pnls = wallets.into_iter()
+        .map(|wallet| {
+            let net_usdc = wallet_cash.get_last(&wallet).unwrap_or_else(BigInt::zero).to_decimal(collateral::USDC_DECIMALS as u64);
+            // Tokens without a CLOB trade yet have no price and count 0, as do tokens of an unknown collateral
+            let mut share_value = BigDecimal::zero();
+            for token_id in wallet_tokens.get_last(format!("tokens:{}", wallet)).unwrap_or_default() {
+                let (Some(balance), Some(price)) = (positions.get_last(format!("{}:{}", wallet, token_id)), prices.get_last(price_key(&token_id))) else {
+                    continue;
+                };
+                let Some(decimals) = collateral::position_decimals(position_collaterals, &token_id) else {
+                    continue;
+                };
+                share_value = share_value + balance.to_decimal(decimals as u64) * price;
+            }
+
+            let total_pnl = net_usdc.clone() + share_value.clone();
+            contract::WalletPnL {
+                wallet,
+                net_usdc: net_usdc.to_string(),
+                share_value: share_value.to_string(),
+                total_pnl: total_pnl.to_string(),
+                block_number: clock.number,
+                updated_at: clock.timestamp,
+            }
+        })
+        .collect();
+
+    contract::WalletPnLs { wallet_pnls }
+}
+
+// Economic view of an order fill: asset id "0" is the USDC side, the other side is the outcome token
+struct FillEconomics {
+    token_id: String,
+    shares: BigInt,
+    notional: BigInt,
+    fee_usdc: BigInt,
+    maker_is_buyer: bool,
+}
+
+fn fill_economics(order_fill: &contract::OrderFilled) -> Option<FillEconomics> {
+    let maker_amount: BigInt = order_fill.maker_amount_filled.parse().ok()?;
+    let taker_amount: BigInt = order_fill.taker_amount_filled.parse().ok()?;
+    let fee: BigInt = order_fill.fee.parse().unwrap_or_default();
+
+    // The maker buys outcome tokens with USDC, the fee is charged on the tokens received
+    if order_fill.maker_asset_id == "0" && order_fill.taker_asset_id != "0" {
+        let fee_usdc = if taker_amount.is_zero() {
+            BigInt::zero()
+        } else {
+            fee * maker_amount.clone() / taker_amount.clone()
+        };
+        return Some(FillEconomics {
+            token_id: order_fill.taker_asset_id.clone(),
+            shares: taker_amount,
+            notional: maker_amount,
+            fee_usdc,
+            maker_is_buyer: true,
+        });
+    }
+
+    // The maker sells outcome tokens for USDC, the fee is charged on the USDC received
+    if order_fill.taker_asset_id == "0" && order_fill.maker_asset_id != "0" {
+        return Some(FillEconomics {
+            token_id: order_fill.maker_asset_id.clone(),
+            shares: maker_amount,
+            notional: taker_amount,
+            fee_usdc: fee,
+            maker_is_buyer: false,
+        });
+    }
+
+    None
+}
+
+// Fills of both exchanges in log order, paired with the exchange that emitted them
+fn exchange_fills<'a>(
+    network: &network::NetworkProfile,
+    ctf_fills: &'a contract::OrderFilledEvents,
+    neg_risk_fills: &'a contract::OrderFilledEvents,
+) -> Vec<(&'a contract::OrderFilled, [u8; 20])> {
+    let mut fills: Vec<_> = ctf_fills.order_filled.iter()
+        .map(|fill| (fill, network.ctf_exchange))
+        .chain(neg_risk_fills.order_filled.iter().map(|fill| (fill, network.neg_risk_ctf_exchange)))
+        .collect();
+    fills.sort_by_key(|(fill, _)| fill.evt_index);
+    fills
+}
+
+// Build the market row from the registry, condition state and accumulated volume stores
+fn build_market_pnl(
+    condition_id: &str,
+    market: Option<&contract::DuneMarketData>,
+    condition: Option<&contract::CtfConditionResolution>,
+    volume: &impl StoreGet<BigInt>,
+    block_number: u64,
+) -> contract::OnChainMarketPnL {
+    let total_volume = volume.get_last(format!("volume:{}", condition_id)).unwrap_or_default();
+    let total_trades = volume.get_last(format!("trades:{}", condition_id)).unwrap_or_default();
+    let total_fees = volume.get_last(format!("fees:{}", condition_id)).unwrap_or_default();
+
+    let mut market_pnl = contract::OnChainMarketPnL {
+        condition_id: condition_id.to_string(),
+        total_volume: total_volume.to_decimal(6).to_string(),
+        total_trades: total_trades.to_string(),
+        total_fees: total_fees.to_decimal(6).to_string(),
+        created_at: market.and_then(|m| m.created_at),
+        is_neg_risk: market.map(|m| m.is_neg_risk).unwrap_or(false),
+        block_number,
+        ..Default::default()
+    };
+
+    if let Some(condition) = condition {
+        market_pnl.question_id = format!("0x{}", Hex(&condition.question_id));
+        if market_pnl.created_at.is_none() && condition.payout_numerators.is_empty() {
+            market_pnl.created_at = condition.evt_block_time;
+        }
+
+        if let Some((winning_outcome, resolution_price)) = resolve_outcome(condition) {
+            market_pnl.winning_outcome = winning_outcome;
+            market_pnl.resolution_price = resolution_price;
+            market_pnl.resolved_at = condition.evt_block_time;
+        }
+    }
+
+    market_pnl
+}
+
+// Winning outcome index (ties comma separated) and its payout ratio from a ConditionResolution
+fn resolve_outcome(condition: &contract::CtfConditionResolution) -> Option<(String, String)> {
+    let numerators: Vec<BigInt> = condition.payout_numerators.iter()
+        .map(|n| n.parse().unwrap_or_default())
+        .collect();
+    let denominator: BigInt = condition.payout_denominator.parse().unwrap_or_default();
+    let max_numerator = numerators.iter().max()?.clone();
+    if denominator.is_zero() {
+        return None;
+    }
+
+    let winning_outcome = numerators.iter().enumerate()
+        .filter(|(_, n)| **n == max_numerator)
+        .map(|(i, _)| i.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let resolution_price = BigDecimal::from(max_numerator) / BigDecimal::from(denominator);
+
+    Some((winning_outcome, resolution_price.to_string()))
+}
+
+// Store handlers have no return value: a body's error fails the module the way a map's Err does
+fn fail_on_error(result: Result<(), substreams::errors::Error>) {
+    if let Err(err) = result {
+        panic!("{:?}", err)
+    }
+}
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+// UTC day number (days since the Unix epoch) of the block
+fn utc_day(clock: &Clock) -> u64 {
+    clock.timestamp.map(|ts| ts.seconds.max(0) as u64 / SECONDS_PER_DAY).unwrap_or(0)
+}
+
+// Build a global snapshot from the counters stored under the given key prefix ("" or "day:<day>:"),
+// active users are always those of `day`
+fn build_global_pnl(
+    stats: &impl StoreGet<BigInt>,
+    prefix: &str,
+    day: u64,
+    period: &str,
+    timestamp: prost_types::Timestamp,
+    block_number: u64,
+) -> contract::OnChainGlobalPnL {
+    let counter = |name: &str| stats.get_last(format!("{}{}", prefix, name)).unwrap_or_default();
+
+    let active_markets = if prefix.is_empty() {
+        // Registered markets that have not been resolved yet
+        let open_markets = counter("markets") - counter("resolved_markets");
+        if open_markets < BigInt::zero() { BigInt::zero() } else { open_markets }
+    } else {
+        counter("markets")
+    };
+
+    contract::OnChainGlobalPnL {
+        total_volume: counter("volume").to_decimal(6).to_string(),
+        total_trades: counter("trades").to_string(),
+        total_fees: counter("fees").to_decimal(6).to_string(),
+        active_users: stats.get_last(format!("day:{}:users", day)).unwrap_or_default().to_string(),
+        active_markets: active_markets.to_string(),
+        resolved_markets: counter("resolved_markets").to_string(),
+        timestamp: Some(timestamp),
+        period: period.to_string(),
+        block_number,
+        total_users: stats.get_last("users").unwrap_or_default().to_string(),
+    }
+}
+
+// Price in USDC per share, truncated to 6 decimals (both amounts use 6 decimals)
+fn price_per_share(usdc_amount: &BigInt, shares: &BigInt) -> String {
+    if shares.is_zero() {
+        return "0".to_string();
+    }
+    fixed_ratio(usdc_amount, shares).to_string()
+}
+
+// numerator / denominator truncated to 6 decimals, computed on integers
+fn fixed_ratio(numerator: &BigInt, denominator: &BigInt) -> BigDecimal {
+    (numerator.clone() * BigInt::from(1_000_000u64) / denominator.clone()).to_decimal(6)
+}
+
+// Raw base units as an exact decimal, e.g. "1500000" with 6 decimals is 1.5
+fn raw_to_decimal(raw: &str, decimals: u64) -> BigDecimal {
+    raw.parse::<BigInt>().unwrap_or_default().to_decimal(decimals)
+}
+
+// Decimal strings written by this module, malformed values count as zero
+fn parse_decimal(value: &str) -> BigDecimal {
+    value.parse().unwrap_or_default()
+}
+
+fn side_label(is_buy: bool) -> String {
+    if is_buy { "BUY" } else { "SELL" }.to_string()
+}
+
+// Enrich an exchange OrderFilled with sides, outcome token and market from the registry
+fn build_clob_trade(
+    order_fill: &contract::OrderFilled,
+    network: &network::NetworkProfile,
+    exchange: &[u8],
+    registry: &impl StoreGet<contract::DuneMarketData>,
+    conditions: &impl StoreGet<contract::CtfConditionResolution>,
+    position_collaterals: &impl StoreGet<contract::CollateralToken>,
+) -> Option<contract::Trade> {
+    let economics = fill_economics(order_fill)?;
+    // Outcome tokens have the decimals of the collateral they were split from, unknown collateral leaves the fill out
+    let decimals = collateral::position_decimals(position_collaterals, &economics.token_id)?;
+    let market = registry.get_last(format!("token:{}", economics.token_id));
+    let question_id = market.as_ref()
+        .and_then(|m| conditions.get_last(format!("condition:{}", m.condition_id)))
+        .map(|condition| format!("0x{}", Hex(&condition.question_id)))
+        .unwrap_or_default();
+
+    Some(contract::Trade {
+        evt_tx_hash: String::new(), // Will be set by caller
+        evt_index: order_fill.evt_index,
+        evt_block_time: None, // Will be set by caller
+        evt_block_number: 0, // Will be set by caller
+        evt_tx_index: 0, // Will be set by caller
+        evt_tx_from: Vec::new(), // Will be set by caller
+        venue: network.venue(exchange).to_string(),
+        exchange_address: format!("0x{}", Hex(exchange)),
+        order_hash: format!("0x{}", Hex(&order_fill.order_hash)),
+        maker_address: format!("0x{}", Hex(&order_fill.maker)),
+        taker_address: format!("0x{}", Hex(&order_fill.taker)),
+        maker_side: side_label(economics.maker_is_buyer),
+        taker_side: side_label(!economics.maker_is_buyer),
+        token_id: economics.token_id.clone(),
+        outcome_index: market.as_ref()
+            .map(|m| if m.token1 == economics.token_id { 1 } else { 0 })
+            .unwrap_or(0),
+        condition_id: market.map(|m| m.condition_id).unwrap_or_default(),
+        question_id,
+        price: price_per_share(&economics.notional, &economics.shares),
+        shares: economics.shares.to_decimal(decimals as u64).to_string(),
+        usdc_amount: economics.notional.to_decimal(decimals as u64).to_string(),
+        fee_usdc: economics.fee_usdc.to_decimal(decimals as u64).to_string(),
+        is_taker_order: order_fill.taker == exchange,
+        decimals,
+    })
+}
+
+// Enrich an FPMMBuy/FPMMSell, the FPMM contract is the maker and the trader the taker
+fn build_fpmm_trade(
+    fpmm_trade: &abi::FpmmTradeData,
+    log: &eth::Log,
+    market: &contract::FpmmFactoryCreation,
+    decimals: u32,
+    conditions: &impl StoreGet<contract::CtfConditionResolution>,
+) -> contract::Trade {
+    let condition_id = market.condition_ids.first()
+        .map(|id| format!("0x{}", Hex(id)))
+        .unwrap_or_default();
+    let question_id = conditions.get_last(format!("condition:{}", condition_id))
+        .map(|condition| format!("0x{}", Hex(&condition.question_id)))
+        .unwrap_or_default();
+
+    // Price excludes the LP fee: buys pay it on top of the investment, sells deduct it from the return
+    let (taker_is_buyer, traded_usdc) = match fpmm_trade.side {
+        abi::FpmmTradeSide::Buy => (true, fpmm_trade.collateral_amount.clone() - fpmm_trade.fee_amount.clone()),
+        abi::FpmmTradeSide::Sell => (false, fpmm_trade.collateral_amount.clone() + fpmm_trade.fee_amount.clone()),
+    };
+
+    contract::Trade {
+        evt_tx_hash: String::new(), // Will be set by caller
+        evt_index: log.block_index,
+        evt_block_time: None, // Will be set by caller
+        evt_block_number: 0, // Will be set by caller
+        evt_tx_index: 0, // Will be set by caller
+        evt_tx_from: Vec::new(), // Will be set by caller
+        venue: "FPMM".to_string(),
+        exchange_address: format!("0x{}", Hex(&log.address)),
+        order_hash: String::new(),
+        maker_address: format!("0x{}", Hex(&log.address)),
+        taker_address: format!("0x{}", Hex(&fpmm_trade.trader)),
+        maker_side: side_label(!taker_is_buyer),
+        taker_side: side_label(taker_is_buyer),
+        token_id: String::new(),
+        outcome_index: fpmm_trade.outcome_index,
+        condition_id,
+        question_id,
+        price: price_per_share(&traded_usdc, &fpmm_trade.outcome_tokens),
+        // Outcome tokens have the decimals of the market's collateral
+        shares: fpmm_trade.outcome_tokens.to_decimal(decimals as u64).to_string(),
+        usdc_amount: fpmm_trade.collateral_amount.to_decimal(decimals as u64).to_string(),
+        fee_usdc: fpmm_trade.fee_amount.to_decimal(decimals as u64).to_string(),
+        is_taker_order: false,
+        decimals,
+    }
+}
+
+// Group a taker order: split its own fill from the maker fills and aggregate size and price
+fn build_order_match(
+    orders_matched: contract::OrdersMatched,
+    fills: Vec<contract::OrderFilled>,
+    network: &network::NetworkProfile,
+    exchange: &[u8],
+    registry: &impl StoreGet<contract::DuneMarketData>,
+) -> contract::OrderMatch {
+    let (mut taker_fills, maker_fills): (Vec<_>, Vec<_>) = fills.into_iter()
+        .partition(|fill| fill.taker == exchange && fill.order_hash == orders_matched.taker_order_hash);
+    let taker_fill = taker_fills.pop();
+
+    // The matched amounts are the taker order's own, seen as a fill with the taker order as maker
+    let taker_order = contract::OrderFilled {
+        maker_asset_id: orders_matched.maker_asset_id.clone(),
+        taker_asset_id: orders_matched.taker_asset_id.clone(),
+        maker_amount_filled: orders_matched.maker_amount_filled.clone(),
+        taker_amount_filled: orders_matched.taker_amount_filled.clone(),
+        fee: taker_fill.as_ref().map(|fill| fill.fee.clone()).unwrap_or_default(),
+        ..Default::default()
+    };
+
+    let mut order_match = contract::OrderMatch {
+        venue: network.venue(exchange).to_string(),
+        exchange_address: format!("0x{}", Hex(exchange)),
+        orders_matched: Some(orders_matched),
+        taker_fill,
+        maker_fills,
+        ..Default::default()
+    };
+
+    if let Some(economics) = fill_economics(&taker_order) {
+        order_match.taker_side = side_label(economics.maker_is_buyer);
+        order_match.condition_id = registry.get_last(format!("token:{}", economics.token_id))
+            .map(|market| market.condition_id)
+            .unwrap_or_default();
+        order_match.average_price = price_per_share(&economics.notional, &economics.shares);
+        order_match.size = economics.shares.to_decimal(6).to_string();
+        order_match.usdc_amount = economics.notional.to_decimal(6).to_string();
+        order_match.fee_usdc = economics.fee_usdc.to_decimal(6).to_string();
+        order_match.token_id = economics.token_id;
+    }
+
+    order_match
+}
+
+// Conditions, positions and ERC1155 transfers of the CTF contract, shared by map_ctf_events and the replay tests
+fn ctf_events(network: &network::NetworkProfile, blk: &eth::Block) -> contract::CtfEvents {
+    let mut events = contract::CtfEvents::default();
+
+    for origin in events::block_logs(blk) {
+        let log = origin.log;
+        if log.address != network.ctf {
+            continue;
+        }
+
+        // Conditions and positions, plus the CTF's own ERC1155 transfers
+        match abi::route(network, log) {
+            Some(abi::DecodedEvent::Ctf(abi::CtfEventType::ConditionPreparation(mut evt))) => {
+                evt.set_origin(&origin, blk);
+                events.condition_preparations.push(evt);
+            },
+            Some(abi::DecodedEvent::Ctf(abi::CtfEventType::ConditionResolution(mut evt))) => {
+                evt.set_origin(&origin, blk);
+                events.condition_resolutions.push(evt);
+            },
+            Some(abi::DecodedEvent::Ctf(abi::CtfEventType::PositionSplit(mut evt))) => {
+                evt.set_origin(&origin, blk);
+                events.position_splits.push(evt);
+            },
+            Some(abi::DecodedEvent::Ctf(abi::CtfEventType::PositionMerge(mut evt))) => {
+                evt.set_origin(&origin, blk);
+                events.position_merges.push(evt);
+            },
+            Some(abi::DecodedEvent::Ctf(abi::CtfEventType::PositionRedeem(mut evt))) => {
+                evt.set_origin(&origin, blk);
+                events.position_redemptions.push(evt);
+            },
+            Some(abi::DecodedEvent::Erc1155TransferSingle(transfer)) => {
+                let mut evt = contract::CtfTransferSingle {
+                    operator: transfer.operator,
+                    from: transfer.from,
+                    to: transfer.to,
+                    token_id: transfer.id,
+                    value: transfer.value,
+                    ..Default::default()
+                };
+                evt.set_origin(&origin, blk);
+                events.transfer_singles.push(evt);
+            },
+            Some(abi::DecodedEvent::Erc1155TransferBatch(batch)) => {
+                let mut evt = contract::CtfTransferBatch {
+                    operator: batch.operator,
+                    from: batch.from,
+                    to: batch.to,
+                    token_ids: batch.ids,
+                    values: batch.values,
+                    ..Default::default()
+                };
+                evt.set_origin(&origin, blk);
+                events.transfer_batches.push(evt);
+            },
+            _ => {},
+        }
+    }
+
+    events
+}
+
+// Position and cash keys use the 0x wallet address, the zero address, excluded and unwatched addresses are skipped
+fn tracked_wallet(params: &params::Params, address: &[u8]) -> Option<String> {
+    let wallet = format!("0x{}", Hex(address));
+    if is_zero_address(address) || params.is_excluded(address) || !params.watches(address) {
+        return None;
+    }
+    Some(wallet)
+}
+
+// store_positions deltas as (ordinal, <wallet>:<token_id>, raw amount), senders negative and receivers positive
+fn position_deltas(params: &params::Params, ctf_events: &contract::CtfEvents) -> Vec<(u64, String, BigInt)> {
+    let mut deltas = Vec::new();
+    let mut add_position = |ordinal: u32, address: &[u8], token_id: &str, value: BigInt| {
+        if let Some(wallet) = tracked_wallet(params, address) {
+            deltas.push((ordinal as u64, format!("{}:{}", wallet, token_id), value));
+        }
+    };
+
+    for transfer in &ctf_events.transfer_singles {
+        let value: BigInt = transfer.value.parse().unwrap_or_default();
+        add_position(transfer.evt_index, &transfer.from, &transfer.token_id, value.neg());
+        add_position(transfer.evt_index, &transfer.to, &transfer.token_id, value);
+    }
+
+    for batch in &ctf_events.transfer_batches {
+        for (token_id, value) in batch.token_ids.iter().zip(&batch.values) {
+            let value: BigInt = value.parse().unwrap_or_default();
+            add_position(batch.evt_index, &batch.from, token_id, value.neg());
+            add_position(batch.evt_index, &batch.to, token_id, value);
+        }
+    }
+
+    deltas
+}
+
+// Outcome tokens minted by each split as (ordinal, token_id, collateral). The CTF mints a split's positions to the
+// stakeholder in a TransferBatch from the zero address right before emitting PositionSplit, in the same transaction
+fn position_collaterals(ctf_events: &contract::CtfEvents) -> Vec<(u64, &str, &[u8])> {
+    let mut minted = Vec::new();
+    for split in &ctf_events.position_splits {
+        let mint = ctf_events.transfer_batches.iter()
+            .filter(|batch| batch.evt_tx_hash == split.evt_tx_hash && batch.evt_index < split.evt_index)
+            .filter(|batch| is_zero_address(&batch.from) && batch.to == split.stakeholder)
+            .max_by_key(|batch| batch.evt_index);
+        if let Some(mint) = mint {
+            minted.extend(mint.token_ids.iter().map(|token_id| (split.evt_index as u64, token_id.as_str(), split.collateral_token.as_slice())));
+        }
+    }
+    minted
+}
+
+// Raw collateral amount in USDC base units, using the registered collateral's decimals, None for an unknown collateral
+fn collateral_amount(collaterals: &impl StoreGet<contract::CollateralToken>, token: &[u8], amount: &str) -> Option<BigInt> {
+    let amount: BigInt = amount.parse().unwrap_or_default();
+    Some(collateral::to_usdc_units(amount, collateral::decimals(collaterals, token)?))
+}
+
+fn price_key(token_id: &str) -> String {
+    format!("price:{}", token_id)
+}
+
+// Polymarket contract logs and token transfers naming a watched wallet in an indexed address topic
+// (maker, taker, from, to, stakeholder, recipient)
+fn touches_watched_wallet(params: &params::Params, log: &eth::Log) -> bool {
+    let is_polymarket_log = params.network.contracts().any(|contract| log.address == contract)
+        || log.topics.first().is_some_and(|topic0| {
+            topic0.as_slice() == abi::TRANSFER_SINGLE_SIG || topic0.as_slice() == abi::TRANSFER_BATCH_SIG || topic0.as_slice() == abi::ERC20_TRANSFER_SIG
+        });
+    is_polymarket_log && log.topics.iter().skip(1).any(|topic| topic.len() == 32 && params.watches(&topic[12..]))
+}
+
+fn calculate_price_from_order_fill(order_fill: &contract::OrderFilled) -> BigDecimal {
     // Calculate price like subgraph: price = takerAmountFilled / makerAmountFilled
-    let maker_amount: f64 = order_fill.maker_amount_filled.parse().unwrap_or(0.0);
-    let taker_amount: f64 = order_fill.taker_amount_filled.parse().unwrap_or(0.0);
+    let maker_amount: BigInt = order_fill.maker_amount_filled.parse().unwrap_or_default();
+    let taker_amount: BigInt = order_fill.taker_amount_filled.parse().unwrap_or_default();
     
-    if maker_amount > 0.0 && taker_amount > 0.0 {
-        taker_amount / maker_amount
+    if maker_amount > BigInt::zero() && taker_amount > BigInt::zero() {
+        fixed_ratio(&taker_amount, &maker_amount)
     } else {
-        1.0 // Default price if amounts are invalid
+        BigDecimal::one() // Default price if amounts are invalid
     }
 }
 
@@ -837,7 +2013,7 @@ fn update_user_pnl(
     user_pnls: &mut HashMap<String, contract::DuneUserPnL>,
     user_addr: &str,
     token_id: &str,
-    amount: &str,
+    amount: &BigDecimal,
     blk: &eth::Block,
     is_receive: bool,
 ) {
@@ -855,27 +2031,26 @@ fn update_user_pnl(
     });
 
     // Update holdings
-    let amount_f64: f64 = amount.parse().unwrap_or(0.0);
-    let multiplier = if is_receive { 1.0 } else { -1.0 };
+    let signed_amount = if is_receive { amount.clone() } else { amount.neg() };
     
     // Find existing holding or create new one
     let mut found = false;
     for holding in &mut user_pnl.holdings {
         if holding.token_id == token_id {
-            let current_amount: f64 = holding.amount.parse().unwrap_or(0.0);
-            holding.amount = (current_amount + (amount_f64 * multiplier)).to_string();
+            holding.amount = (parse_decimal(&holding.amount) + signed_amount.clone()).to_string();
             found = true;
             break;
         }
     }
     
-    if !found && is_receive {
+    // Holdings are this block's net change, a send can come before any receive
+    if !found {
         user_pnl.holdings.push(contract::DuneTokenHolding {
             user_address: user_addr.to_string(),
             token_id: token_id.to_string(),
-            amount: amount_f64.to_string(),
-            latest_price: "1.0".to_string(), // Will be updated with real price data
-            share_value: amount_f64.to_string(),
+            amount: signed_amount.to_string(),
+            latest_price: "1".to_string(), // Will be updated with real price data
+            share_value: signed_amount.to_string(),
         });
     }
 
@@ -899,13 +2074,13 @@ fn update_price_data(
 
     if token_id != "0" {
         // Calculate price from order fill (like Dune query prices CTE)
-        let maker_amount: f64 = order_fill.maker_amount_filled.parse().unwrap_or(0.0);
-        let taker_amount: f64 = order_fill.taker_amount_filled.parse().unwrap_or(0.0);
+        let maker_amount: BigInt = order_fill.maker_amount_filled.parse().unwrap_or_default();
+        let taker_amount: BigInt = order_fill.taker_amount_filled.parse().unwrap_or_default();
         
-        let price = if taker_amount > 0.0 {
-            maker_amount / taker_amount
+        let price = if taker_amount > BigInt::zero() {
+            fixed_ratio(&maker_amount, &taker_amount)
         } else {
-            0.0
+            BigDecimal::zero()
         };
 
         // Update price data with latest trade
@@ -924,37 +2099,37 @@ fn calculate_user_pnl(
     user_pnl: &mut contract::DuneUserPnL,
     price_data: &HashMap<String, contract::DunePriceData>,
 ) {
-    let mut net_usdc = 0.0;
-    let mut share_value = 0.0;
+    let mut net_usdc = BigDecimal::zero();
+    let mut share_value = BigDecimal::zero();
     
     // Calculate net USDC and share value from holdings (like Dune query holders CTE)
     for holding in &mut user_pnl.holdings {
-        let amount: f64 = holding.amount.parse().unwrap_or(0.0);
-        let latest_price: f64 = holding.latest_price.parse().unwrap_or(1.0);
+        let amount = parse_decimal(&holding.amount);
+        let latest_price = holding.latest_price.parse::<BigDecimal>().unwrap_or_else(|_| BigDecimal::one());
         
         if holding.token_id == "USDC" {
-            net_usdc += amount;
+            net_usdc = net_usdc + amount;
         } else {
             // Update with real price data if available
             if let Some(price_info) = price_data.get(&holding.token_id) {
-                let price: f64 = price_info.price.parse().unwrap_or(0.0);
+                let price = parse_decimal(&price_info.price);
                 holding.latest_price = price.to_string();
-                share_value += amount * price;
+                share_value = share_value + amount.clone() * price;
             } else {
-                share_value += amount * latest_price;
+                share_value = share_value + amount.clone() * latest_price.clone();
             }
             holding.share_value = (amount * latest_price).to_string();
         }
     }
     
     // Calculate trading P&L (like Dune query trading_pnl CTE)
-    let trading_pnl = net_usdc + share_value;
+    let trading_pnl = net_usdc.clone() + share_value.clone();
     
     // For now, liq_pnl is 0 (would need to track rewards separately)
-    let liq_pnl = 0.0;
+    let liq_pnl = BigDecimal::zero();
     
     // Calculate total P&L (like Dune query final SELECT)
-    let total_pnl = trading_pnl + liq_pnl;
+    let total_pnl = trading_pnl.clone() + liq_pnl.clone();
     
     // Update user P&L
     user_pnl.net_usdc = net_usdc.to_string();
@@ -964,3 +2139,17 @@ fn calculate_user_pnl(
     user_pnl.total_pnl = total_pnl.to_string();
 }
 
+fn is_zero_address(address: &[u8]) -> bool {
+    address.iter().all(|b| *b == 0)
+}
+
+// Transfers from the zero address mint outcome tokens (splits), transfers to it burn them (merges and redemptions)
+fn erc1155_transfer_type<'a>(from: &[u8], to: &[u8], transfer_type: &'a str) -> &'a str {
+    if is_zero_address(from) {
+        "MINT"
+    } else if is_zero_address(to) {
+        "BURN"
+    } else {
+        transfer_type
+    }
+}
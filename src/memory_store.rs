@@ -0,0 +1,254 @@
+// In-memory stand-in for the substreams store host functions, so offline::Runner drives the same handler bodies as the
+// runtime. Values are kept encoded the way the runtime keeps them (BigInt and int64 as decimal strings, protobuf
+// messages as their encoding, append stores as ;-terminated items) and every write is recorded as the StoreDelta the runtime would emit for the block.
+use prost::Message;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use substreams::pb::substreams::store_delta::Operation;
+use substreams::pb::substreams::StoreDelta;
+use substreams::scalar::{BigDecimal, BigInt};
+use substreams::store::{Appender, Delta, Deltas, StoreAdd, StoreDelete, StoreGet, StoreNew, StoreSet, StoreSetIfNotExists};
+
+// A store valueType: its Rust value and the bytes the runtime stores for it
+pub trait ValueType {
+    type Value;
+    fn encode(value: &Self::Value) -> Vec<u8>;
+    fn decode(bytes: &[u8]) -> Self::Value;
+}
+
+pub struct BigIntValue;
+pub struct BigDecimalValue;
+pub struct Int64Value;
+pub struct ArrayValue;
+pub struct ProtoValue<T>(PhantomData<T>);
+
+impl ValueType for BigIntValue {
+    type Value = BigInt;
+
+    fn encode(value: &BigInt) -> Vec<u8> {
+        value.to_string().into_bytes()
+    }
+    fn decode(bytes: &[u8]) -> BigInt {
+        BigInt::from_store_bytes(bytes)
+    }
+}
+
+impl ValueType for BigDecimalValue {
+    type Value = BigDecimal;
+
+    fn encode(value: &BigDecimal) -> Vec<u8> {
+        value.to_string().into_bytes()
+    }
+    fn decode(bytes: &[u8]) -> BigDecimal {
+        BigDecimal::from_store_bytes(bytes)
+    }
+}
+
+impl ValueType for Int64Value {
+    type Value = i64;
+
+    fn encode(value: &i64) -> Vec<u8> {
+        value.to_string().into_bytes()
+    }
+    fn decode(bytes: &[u8]) -> i64 {
+        std::str::from_utf8(bytes).ok().and_then(|value| value.parse().ok()).unwrap_or_default()
+    }
+}
+
+// A string append store read through StoreGetArray
+impl ValueType for ArrayValue {
+    type Value = Vec<String>;
+
+    fn encode(value: &Vec<String>) -> Vec<u8> {
+        value.iter().map(|item| format!("{};", item)).collect::<String>().into_bytes()
+    }
+    fn decode(bytes: &[u8]) -> Vec<String> {
+        String::from_utf8_lossy(bytes).split(';').filter(|item| !item.is_empty()).map(str::to_string).collect()
+    }
+}
+
+impl<T: Message + Default> ValueType for ProtoValue<T> {
+    type Value = T;
+
+    fn encode(value: &T) -> Vec<u8> {
+        value.encode_to_vec()
+    }
+    fn decode(bytes: &[u8]) -> T {
+        T::decode(bytes).unwrap_or_default()
+    }
+}
+
+pub type MemoryStoreBigInt = MemoryStore<BigIntValue>;
+pub type MemoryStoreBigDecimal = MemoryStore<BigDecimalValue>;
+pub type MemoryStoreInt64 = MemoryStore<Int64Value>;
+pub type MemoryStoreArray = MemoryStore<ArrayValue>;
+pub type MemoryStoreProto<T> = MemoryStore<ProtoValue<T>>;
+
+// One store module's state across blocks, and the deltas of the block being run
+pub struct MemoryStore<V> {
+    values: RefCell<HashMap<String, Vec<u8>>>,
+    deltas: RefCell<Vec<StoreDelta>>,
+    value_type: PhantomData<V>,
+}
+
+impl<V> Default for MemoryStore<V> {
+    fn default() -> Self {
+        MemoryStore {
+            values: RefCell::new(HashMap::new()),
+            deltas: RefCell::new(Vec::new()),
+            value_type: PhantomData,
+        }
+    }
+}
+
+impl<V: ValueType> MemoryStore<V> {
+    // This block's deltas as a `mode: deltas` input
+    pub fn deltas<T: Delta + From<StoreDelta>>(&self) -> Deltas<T> {
+        Deltas::new(self.deltas.borrow().clone())
+    }
+
+    // Ends the block: its deltas, in write order
+    pub fn take_deltas(&self) -> Vec<StoreDelta> {
+        self.deltas.take()
+    }
+
+    fn write(&self, ordinal: u64, key: &str, new_value: Option<Vec<u8>>) {
+        let mut values = self.values.borrow_mut();
+        let old_value = match &new_value {
+            Some(value) => values.insert(key.to_string(), value.clone()),
+            None => values.remove(key),
+        };
+        let operation = match (&old_value, &new_value) {
+            (_, None) => Operation::Delete,
+            (None, Some(_)) => Operation::Create,
+            (Some(_), Some(_)) => Operation::Update,
+        };
+        self.deltas.borrow_mut().push(StoreDelta {
+            operation: operation as i32,
+            ordinal,
+            key: key.to_string(),
+            old_value: old_value.unwrap_or_default(),
+            new_value: new_value.unwrap_or_default(),
+        });
+    }
+
+    fn read(&self, key: &str) -> Option<V::Value> {
+        self.values.borrow().get(key).map(|bytes| V::decode(bytes))
+    }
+
+    // Value before the first of this block's writes at or after `ordinal`, the current one when there is none
+    fn read_before(&self, ordinal: u64, key: &str) -> Option<V::Value> {
+        let deltas = self.deltas.borrow();
+        match deltas.iter().find(|delta| delta.key == key && delta.ordinal >= ordinal) {
+            Some(delta) if delta.operation == Operation::Create as i32 => None,
+            Some(delta) => Some(V::decode(&delta.old_value)),
+            None => self.read(key),
+        }
+    }
+}
+
+impl<V> StoreNew for MemoryStore<V> {
+    fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<V: ValueType> StoreDelete for MemoryStore<V> {
+    fn delete_prefix(&self, ord: i64, prefix: &String) {
+        let mut keys: Vec<String> = self.values.borrow().keys().filter(|key| key.starts_with(prefix.as_str())).cloned().collect();
+        keys.sort();
+        for key in keys {
+            self.write(ord as u64, &key, None);
+        }
+    }
+}
+
+impl<V: ValueType> StoreGet<V::Value> for MemoryStore<V> {
+    fn new(_idx: u32) -> Self {
+        Self::default()
+    }
+
+    // Value once the writes up to `ord` are applied
+    fn get_at<K: AsRef<str>>(&self, ord: u64, key: K) -> Option<V::Value> {
+        self.read_before(ord + 1, key.as_ref())
+    }
+
+    fn get_last<K: AsRef<str>>(&self, key: K) -> Option<V::Value> {
+        self.read(key.as_ref())
+    }
+
+    // Value at the start of the block
+    fn get_first<K: AsRef<str>>(&self, key: K) -> Option<V::Value> {
+        self.read_before(0, key.as_ref())
+    }
+
+    fn has_at<K: AsRef<str>>(&self, ord: u64, key: K) -> bool {
+        self.get_at(ord, key).is_some()
+    }
+
+    fn has_last<K: AsRef<str>>(&self, key: K) -> bool {
+        self.values.borrow().contains_key(key.as_ref())
+    }
+
+    fn has_first<K: AsRef<str>>(&self, key: K) -> bool {
+        self.get_first(key).is_some()
+    }
+}
+
+impl<V: ValueType> StoreSet<V::Value> for MemoryStore<V> {
+    fn set<K: AsRef<str>>(&self, ord: u64, key: K, value: &V::Value) {
+        self.write(ord, key.as_ref(), Some(V::encode(value)));
+    }
+
+    fn set_many<K: AsRef<str>>(&self, ord: u64, keys: &Vec<K>, value: &V::Value) {
+        for key in keys {
+            self.set(ord, key, value);
+        }
+    }
+}
+
+impl<V: ValueType> StoreSetIfNotExists<V::Value> for MemoryStore<V> {
+    fn set_if_not_exists<K: AsRef<str>>(&self, ord: u64, key: K, value: &V::Value) {
+        if !self.has_last(&key) {
+            self.set(ord, key, value);
+        }
+    }
+
+    fn set_if_not_exists_many<K: AsRef<str>>(&self, ord: u64, keys: &Vec<K>, value: &V::Value) {
+        for key in keys {
+            self.set_if_not_exists(ord, key, value);
+        }
+    }
+}
+
+impl<T: AsRef<BigInt>> StoreAdd<T> for MemoryStoreBigInt {
+    fn add<K: AsRef<str>>(&self, ord: u64, key: K, value: T) {
+        let sum = self.read(key.as_ref()).unwrap_or_else(BigInt::zero) + value.as_ref().clone();
+        self.write(ord, key.as_ref(), Some(BigIntValue::encode(&sum)));
+    }
+
+    fn add_many<K: AsRef<str>>(&self, ord: u64, keys: &Vec<K>, value: T) {
+        for key in keys {
+            self.add(ord, key, value.as_ref());
+        }
+    }
+}
+
+impl Appender<String> for MemoryStoreArray {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn append<K: AsRef<str>>(&self, ord: u64, key: K, item: String) {
+        let mut items = self.read(key.as_ref()).unwrap_or_default();
+        items.push(item);
+        self.write(ord, key.as_ref(), Some(ArrayValue::encode(&items)));
+    }
+
+    fn append_all<K: AsRef<str>>(&self, ord: u64, key: K, items: Vec<String>) {
+        for item in items {
+            self.append(ord, &key, item);
+        }
+    }
+}
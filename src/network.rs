@@ -0,0 +1,127 @@
+// Network profiles: the Polymarket contract set of each chain, chosen with the "network=" param
+// Contracts a chain does not have are left as the zero address, which never emits logs
+use hex_literal::hex;
+
+const NONE: [u8; 20] = [0u8; 20];
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NetworkProfile {
+    pub name: &'static str,
+    pub ctf: [u8; 20],
+    pub ctf_exchange: [u8; 20],
+    pub neg_risk_ctf_exchange: [u8; 20],
+    pub neg_risk_adapter: [u8; 20],
+    pub fpmm_factory: [u8; 20],
+    pub usdc: [u8; 20],
+    pub uma_merkle_distributor: [u8; 20],
+    pub usdc_merkle_distributor: [u8; 20],
+    // Other protocol addresses left out of user P&L by default
+    pub extra_excluded: &'static [[u8; 20]],
+    // Collateral tokens with known metadata, others are registered as unknown and left unvalued
+    pub collaterals: &'static [KnownCollateral],
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct KnownCollateral {
+    pub address: [u8; 20],
+    pub symbol: &'static str,
+    pub decimals: u32,
+}
+
+// Polygon mainnet, addresses from the Dune query
+pub const POLYGON: NetworkProfile = NetworkProfile {
+    name: "polygon",
+    ctf: hex!("4d97dcd97ec945f40cf65f87097ace5ea0476045"),
+    ctf_exchange: hex!("4bfb41d5b3570defd03c39a9a4d8de6bd8b8982e"),
+    neg_risk_ctf_exchange: hex!("c5d563a36ae78145c45a50134d48a1215220f80a"),
+    neg_risk_adapter: hex!("d91e80cf2e7be2e162c6513ced06f1dd0da35296"),
+    fpmm_factory: hex!("a5ef39c3d3e10d0b270233af41cac69796b12966"),
+    usdc: hex!("2791bca1f2de4661ed88a30c99a7a9449aa84174"),
+    // The address listed here before is the NegRisk WrappedCollateral below, whose logs were read as UMA claims.
+    // Left unset until the distributor's own address is confirmed
+    uma_merkle_distributor: NONE,
+    usdc_merkle_distributor: hex!("c288480574783bd7615170660d71753378159c47"),
+    extra_excluded: &[
+        hex!("78769d50be1763ed1ca0d5e878d93f05aabff29e"), // Neg Risk Fee Module
+        hex!("a2bd9cc3e04996ca683c834e4d86a016f6bbde5a"), // Additional excluded
+        hex!("3a3bd7bb9528e159577f7c2e685cc81a765002e2"), // NegRisk WrappedCollateral
+    ],
+    collaterals: &[
+        KnownCollateral { address: hex!("2791bca1f2de4661ed88a30c99a7a9449aa84174"), symbol: "USDC.e", decimals: 6 },
+        KnownCollateral { address: hex!("3c499c542cef5e3811e1192ce70d8cc03d5c3359"), symbol: "USDC", decimals: 6 },
+        // NegRisk adapter collateral, wraps USDC.e 1:1
+        KnownCollateral { address: hex!("3a3bd7bb9528e159577f7c2e685cc81a765002e2"), symbol: "WCOL", decimals: 6 },
+    ],
+};
+
+// Amoy testnet, Polymarket's CLOB deployment (no FPMM factory or merkle distributors)
+pub const AMOY: NetworkProfile = NetworkProfile {
+    name: "amoy",
+    ctf: hex!("69308fb512518e39f9b16112fa8d994f4e2bf8bb"),
+    ctf_exchange: hex!("dfe02eb6733538f8ea35d585af8de5958ad99e40"),
+    neg_risk_ctf_exchange: hex!("c5d563a36ae78145c45a50134d48a1215220f80a"),
+    neg_risk_adapter: hex!("d91e80cf2e7be2e162c6513ced06f1dd0da35296"),
+    fpmm_factory: NONE,
+    usdc: hex!("9c4e1703476e875070ee25b56a58b008cfb8fa78"),
+    uma_merkle_distributor: NONE,
+    usdc_merkle_distributor: NONE,
+    extra_excluded: &[],
+    collaterals: &[
+        KnownCollateral { address: hex!("9c4e1703476e875070ee25b56a58b008cfb8fa78"), symbol: "USDC", decimals: 6 },
+    ],
+};
+
+pub const NETWORKS: [&NetworkProfile; 2] = [&POLYGON, &AMOY];
+
+impl NetworkProfile {
+    pub fn by_name(name: &str) -> Option<&'static NetworkProfile> {
+        NETWORKS.into_iter().find(|network| network.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn is_exchange(&self, address: &[u8]) -> bool {
+        address == self.ctf_exchange || address == self.neg_risk_ctf_exchange
+    }
+
+    pub fn is_merkle_distributor(&self, address: &[u8]) -> bool {
+        address != NONE && (address == self.uma_merkle_distributor || address == self.usdc_merkle_distributor)
+    }
+
+    pub fn known_collateral(&self, address: &[u8]) -> Option<&'static KnownCollateral> {
+        self.collaterals.iter().find(|collateral| collateral.address == address)
+    }
+
+    // Venue label of an exchange address, see contract.Trade
+    pub fn venue(&self, exchange: &[u8]) -> &'static str {
+        if exchange == self.neg_risk_ctf_exchange { "NEG_RISK" } else { "CLOB" }
+    }
+
+    // Contracts whose logs can carry wallet activity (see index_wallet_activity)
+    pub fn contracts(&self) -> impl Iterator<Item = &[u8; 20]> {
+        [
+            &self.ctf_exchange,
+            &self.neg_risk_ctf_exchange,
+            &self.fpmm_factory,
+            &self.ctf,
+            &self.usdc,
+            &self.uma_merkle_distributor,
+            &self.usdc_merkle_distributor,
+        ].into_iter().filter(|address| **address != NONE)
+    }
+
+    // Excluded addresses from the Dune query, plus the NegRisk exchange and adapter and the zero address
+    pub fn default_excluded(&self) -> Vec<[u8; 20]> {
+        let mut excluded: Vec<[u8; 20]> = [
+            self.ctf,
+            self.ctf_exchange,
+            self.neg_risk_ctf_exchange,
+            self.neg_risk_adapter,
+            self.uma_merkle_distributor,
+            self.fpmm_factory,
+        ].into_iter()
+            .chain(self.extra_excluded.iter().copied())
+            .filter(|address| *address != NONE)
+            .collect();
+        excluded.push(NONE);
+        excluded
+    }
+}
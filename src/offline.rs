@@ -0,0 +1,216 @@
+// Host-side runner for the offline replay tool: the P&L module graph run on local blocks, no endpoint needed.
+// Each block goes through the handler bodies in dependency order with the same params for every module. Stores are
+// memory_store::MemoryStore, kept across blocks, and outputs are protobuf encoded as the substreams runtime emits them.
+// The raw event maps and the sinks are left out.
+//
+// blockFilters: map_pure_dune_pnl is skipped (empty output) on blocks index_wallet_activity gives no wallet_activity
+// key, as the runtime does. The ethcommon:index_events filters (evt_addr/evt_sig) are not evaluated: each of those
+// modules only reads the logs its filter matches, so a block the filter skips gives the same empty output.
+use super::*;
+use memory_store::{MemoryStoreArray, MemoryStoreBigDecimal, MemoryStoreBigInt, MemoryStoreInt64, MemoryStoreProto};
+use prost::Message;
+
+pub use substreams_ethereum::pb::eth::v2::{self as eth, Block};
+
+// (module, output message) in the order Runner::run produces them
+pub const MODULES: [(&str, &str); 16] = [
+    ("map_ctf_exchange_token_registered", "contract.v1.TokenRegisteredEvents"),
+    ("map_neg_risk_ctf_exchange_token_registered", "contract.v1.NegRiskTokenRegisteredEvents"),
+    ("map_fpmm_factory_creation", "contract.v1.FpmmFactoryEvents"),
+    ("map_ctf_exchange_order_filled", "contract.v1.OrderFilledEvents"),
+    ("map_neg_risk_ctf_exchange_order_filled", "contract.v1.OrderFilledEvents"),
+    ("map_ctf_events", "contract.v1.CtfEvents"),
+    ("map_market_pnl", "contract.v1.OnChainMarketPnLs"),
+    ("map_global_pnl", "contract.v1.OnChainGlobalPnLs"),
+    ("map_trades", "contract.v1.Trades"),
+    ("map_order_matches", "contract.v1.OrderMatches"),
+    ("map_exchange_events", "contract.v1.ExchangeEvents"),
+    ("map_wallet_pnl", "contract.v1.WalletPnLs"),
+    ("map_pure_dune_pnl", "contract.v1.PureDunePnL"),
+    ("map_diagnostics", "contract.v1.BlockDiagnostics"),
+    ("map_pure_dune_pnl_v2", "contract.v2.PureDunePnL"),
+    ("map_trades_v2", "contract.v2.Trades"),
+];
+
+// (store, valueType) in the order Runner::run writes them, proto: prefix left out
+pub const STORES: [(&str, &str); 13] = [
+    ("store_market_registry", "contract.v1.DuneMarketData"),
+    ("store_conditions", "contract.v1.CtfConditionResolution"),
+    ("store_collateral_tokens", "contract.v1.CollateralToken"),
+    ("store_position_collaterals", "contract.v1.CollateralToken"),
+    ("store_fpmm_markets", "contract.v1.FpmmFactoryCreation"),
+    ("store_market_volume", "bigint"),
+    ("store_global_first_seen", "int64"),
+    ("store_global_stats", "bigint"),
+    ("store_global_day", "int64"),
+    ("store_positions", "bigint"),
+    ("store_wallet_cash", "bigint"),
+    ("store_token_prices", "bigdecimal"),
+    ("store_wallet_tokens", "string"),
+];
+
+pub struct ModuleOutput {
+    pub module: &'static str,
+    pub type_name: &'static str,
+    pub data: Vec<u8>,
+}
+
+// A store key written by a block, values as the store holds them (decimal strings, or encoded protos), empty when
+// the key did not exist before or was deleted
+pub struct StoreDelta {
+    pub module: &'static str,
+    pub value_type: &'static str,
+    pub ordinal: u64,
+    pub key: String,
+    pub old_value: Vec<u8>,
+    pub new_value: Vec<u8>,
+}
+
+#[derive(Default)]
+pub struct BlockOutputs {
+    pub outputs: Vec<ModuleOutput>,
+    pub store_deltas: Vec<StoreDelta>,
+}
+
+pub struct Runner {
+    module_params: String,
+    params: params::Params,
+    market_registry: MemoryStoreProto<contract::DuneMarketData>,
+    conditions: MemoryStoreProto<contract::CtfConditionResolution>,
+    collateral_tokens: MemoryStoreProto<contract::CollateralToken>,
+    position_collaterals: MemoryStoreProto<contract::CollateralToken>,
+    fpmm_markets: MemoryStoreProto<contract::FpmmFactoryCreation>,
+    market_volume: MemoryStoreBigInt,
+    global_first_seen: MemoryStoreInt64,
+    global_stats: MemoryStoreBigInt,
+    global_day: MemoryStoreInt64,
+    positions: MemoryStoreBigInt,
+    wallet_cash: MemoryStoreBigInt,
+    token_prices: MemoryStoreBigDecimal,
+    wallet_tokens: MemoryStoreArray,
+}
+
+impl Runner {
+    // `params` is the module params string, as passed with -p
+    pub fn new(params: &str) -> Result<Runner, substreams::errors::Error> {
+        Ok(Runner {
+            module_params: params.to_string(),
+            params: params::Params::parse(params)?,
+            market_registry: Default::default(),
+            conditions: Default::default(),
+            collateral_tokens: Default::default(),
+            position_collaterals: Default::default(),
+            fpmm_markets: Default::default(),
+            market_volume: Default::default(),
+            global_first_seen: Default::default(),
+            global_stats: Default::default(),
+            global_day: Default::default(),
+            positions: Default::default(),
+            wallet_cash: Default::default(),
+            token_prices: Default::default(),
+            wallet_tokens: Default::default(),
+        })
+    }
+
+    pub fn run(&mut self, blk: &Block) -> Result<BlockOutputs, substreams::errors::Error> {
+        let mut outputs = BlockOutputs::default();
+        let network = self.params.network;
+        let clock = Clock {
+            id: Hex(&blk.hash).to_string(),
+            number: blk.number,
+            timestamp: Some(blk.timestamp().to_owned()),
+        };
+
+        let ctf_registrations = ctf_token_registrations(network, blk);
+        let neg_risk_registrations = neg_risk_token_registrations(network, blk);
+        let creations = fpmm_creations(network, blk);
+        let ctf_fills = order_fills(network, &network.ctf_exchange, blk);
+        let neg_risk_fills = order_fills(network, &network.neg_risk_ctf_exchange, blk);
+        let ctf_events = ctf_events(network, blk);
+        outputs.push("map_ctf_exchange_token_registered", &ctf_registrations);
+        outputs.push("map_neg_risk_ctf_exchange_token_registered", &neg_risk_registrations);
+        outputs.push("map_fpmm_factory_creation", &creations);
+        outputs.push("map_ctf_exchange_order_filled", &ctf_fills);
+        outputs.push("map_neg_risk_ctf_exchange_order_filled", &neg_risk_fills);
+        outputs.push("map_ctf_events", &ctf_events);
+
+        // Lookup stores
+        register_markets(&ctf_registrations, &neg_risk_registrations, &self.market_registry);
+        set_conditions(&ctf_events, &self.conditions);
+        set_collateral_tokens(&self.module_params, &ctf_events, &creations, &self.collateral_tokens)?;
+        set_position_collaterals(&ctf_events, &self.collateral_tokens, &self.position_collaterals);
+        set_fpmm_markets(&creations, &self.fpmm_markets);
+
+        // Market and global P&L
+        let fills = (&ctf_fills, &neg_risk_fills);
+        let registrations = (&ctf_registrations, &neg_risk_registrations);
+        add_market_volume(&self.module_params, &ctf_fills, &neg_risk_fills, &self.market_registry, &self.market_volume)?;
+        let market_pnls = market_pnls(network, blk, fills, registrations, &ctf_events, &self.market_registry, &self.conditions, &self.market_volume);
+        outputs.push("map_market_pnl", &market_pnls);
+
+        let day = utc_day(&clock);
+        set_global_first_seen(&self.module_params, day, fills, registrations, &self.market_registry, &self.global_first_seen)?;
+        add_global_stats(&self.module_params, day, fills, &ctf_events, &self.global_first_seen.deltas(), &self.market_registry, &self.global_stats)?;
+        set_global_day(&clock, &self.global_day);
+        outputs.push("map_global_pnl", &global_pnls(&clock, &self.global_day.deltas(), &self.global_stats));
+
+        // Trades and exchange events
+        let trades = trades(network, blk, &self.market_registry, &self.conditions, &self.fpmm_markets, &self.collateral_tokens, &self.position_collaterals);
+        outputs.push("map_trades", &trades);
+        outputs.push("map_order_matches", &order_matches(network, blk, &self.market_registry));
+        outputs.push("map_exchange_events", &exchange_events(network, blk));
+
+        // Wallet positions, cash and P&L
+        add_positions(&self.module_params, &ctf_events, &self.positions)?;
+        add_wallet_cash_flows(&self.module_params, fills, &ctf_events, &self.collateral_tokens, &self.wallet_cash)?;
+        set_token_prices(&trades, &self.token_prices);
+        append_wallet_tokens(&self.positions.deltas(), &self.wallet_tokens);
+        let wallet_pnls = wallet_pnls(&self.params, &clock, fills, &ctf_events, &self.collateral_tokens, &self.positions, &self.wallet_cash, &self.wallet_tokens, &self.token_prices, &self.position_collaterals);
+        outputs.push("map_wallet_pnl", &wallet_pnls);
+
+        let pnl = if has_wallet_activity(&self.params, blk) {
+            pure_dune_pnl(&self.params, blk)
+        } else {
+            contract::PureDunePnL::default()
+        };
+        outputs.push("map_pure_dune_pnl", &pnl);
+        outputs.push("map_diagnostics", &diagnostics::block_diagnostics(network, blk, pnl.dropped_records.clone()));
+        outputs.push("map_pure_dune_pnl_v2", &v2::pure_dune_pnl(pnl));
+        outputs.push("map_trades_v2", &v2::trades(trades));
+
+        let store_deltas = [
+            self.market_registry.take_deltas(),
+            self.conditions.take_deltas(),
+            self.collateral_tokens.take_deltas(),
+            self.position_collaterals.take_deltas(),
+            self.fpmm_markets.take_deltas(),
+            self.market_volume.take_deltas(),
+            self.global_first_seen.take_deltas(),
+            self.global_stats.take_deltas(),
+            self.global_day.take_deltas(),
+            self.positions.take_deltas(),
+            self.wallet_cash.take_deltas(),
+            self.token_prices.take_deltas(),
+            self.wallet_tokens.take_deltas(),
+        ];
+        for ((module, value_type), deltas) in STORES.into_iter().zip(store_deltas) {
+            outputs.store_deltas.extend(deltas.into_iter().map(|delta| StoreDelta {
+                module,
+                value_type,
+                ordinal: delta.ordinal,
+                key: delta.key,
+                old_value: delta.old_value,
+                new_value: delta.new_value,
+            }));
+        }
+
+        Ok(outputs)
+    }
+}
+
+impl BlockOutputs {
+    fn push(&mut self, module: &'static str, message: &impl Message) {
+        let (module, type_name) = MODULES.into_iter().find(|(name, _)| *name == module).expect("module not in MODULES");
+        self.outputs.push(ModuleOutput { module, type_name, data: message.encode_to_vec() });
+    }
+}
@@ -0,0 +1,94 @@
+// Module params: "&"-separated key=value pairs, e.g. "network=amoy&wallets=0xabc...,0xdef...&excluded=0x123..."
+// An empty string keeps the defaults (Polygon, no wallet filtering, Dune excluded addresses)
+use crate::network::{self, NetworkProfile};
+use substreams::errors::Error;
+use substreams::Hex;
+
+#[derive(Debug)]
+pub struct Params {
+    // Contract set the modules read addresses from
+    pub network: &'static NetworkProfile,
+    // Only events touching these wallets are kept, empty means every wallet
+    pub wallets: Vec<[u8; 20]>,
+    // Protocol addresses left out of user P&L, "excluded=" replaces the whole default list
+    pub excluded: Vec<[u8; 20]>,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            network: &network::POLYGON,
+            wallets: Vec::new(),
+            excluded: network::POLYGON.default_excluded(),
+        }
+    }
+}
+
+impl Params {
+    pub fn parse(params: &str) -> Result<Params, Error> {
+        let mut parsed = Params::default();
+        let mut excluded = None;
+
+        for pair in params.split('&').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=')
+                .ok_or_else(|| Error::msg(format!("invalid param {:?}, expected key=value", pair)))?;
+            match key.trim() {
+                "network" => {
+                    parsed.network = NetworkProfile::by_name(value.trim())
+                        .ok_or_else(|| Error::msg(format!("unknown network {:?}", value)))?;
+                },
+                "wallets" => parsed.wallets = parse_addresses(value)?,
+                "excluded" => excluded = Some(parse_addresses(value)?),
+                other => return Err(Error::msg(format!("unknown param {:?}", other))),
+            }
+        }
+
+        // The default exclusions follow the network, whatever the key order
+        parsed.excluded = excluded.unwrap_or_else(|| parsed.network.default_excluded());
+
+        Ok(parsed)
+    }
+
+    pub fn is_wallet_filtered(&self) -> bool {
+        !self.wallets.is_empty()
+    }
+
+    // True when no wallet filter is set or the 20-byte address is one of the wallets
+    pub fn watches(&self, address: &[u8]) -> bool {
+        self.wallets.is_empty() || self.wallets.iter().any(|wallet| wallet.as_slice() == address)
+    }
+
+    // Same as watches for hex strings, with or without 0x and left padded to 32 bytes or not
+    pub fn watches_hex(&self, address: &str) -> bool {
+        self.wallets.is_empty() || hex_address(address).is_some_and(|address| self.watches(&address))
+    }
+
+    // Excluded addresses are compared as 20-byte values, whatever the hex casing
+    pub fn is_excluded(&self, address: &[u8]) -> bool {
+        self.excluded.iter().any(|excluded| excluded.as_slice() == address)
+    }
+}
+
+// Last 20 bytes of a hex address, with or without 0x and left padded to 32 bytes or not
+pub fn hex_address(address: &str) -> Option<[u8; 20]> {
+    let address = address.trim_start_matches("0x");
+    if address.len() < 40 || !address.is_ascii() {
+        return None;
+    }
+    decode_address(&address[address.len() - 40..])
+}
+
+fn parse_addresses(value: &str) -> Result<Vec<[u8; 20]>, Error> {
+    value.split(',')
+        .map(str::trim)
+        .filter(|address| !address.is_empty())
+        .map(|address| {
+            decode_address(address.trim_start_matches("0x"))
+                .ok_or_else(|| Error::msg(format!("invalid address {:?}", address)))
+        })
+        .collect()
+}
+
+fn decode_address(hex: &str) -> Option<[u8; 20]> {
+    Hex::decode(hex).ok()?.try_into().ok()
+}
@@ -28,6 +28,12 @@ pub struct PureDunePnL {
     pub block_number: u64,
     #[prost(message, optional, tag="12")]
     pub block_timestamp: ::core::option::Option<::prost_types::Timestamp>,
+    /// Logs of failed transactions and reverted calls left out of this block
+    #[prost(uint64, tag="13")]
+    pub skipped_logs: u64,
+    /// Decoded events left out of this block, by reason
+    #[prost(message, repeated, tag="14")]
+    pub dropped_records: ::prost::alloc::vec::Vec<DroppedRecords>,
 }
 /// Dune User P&L - Exact match to Dune query output
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -38,7 +44,7 @@ pub struct DuneUserPnL {
     /// net_usdc from Dune query
     #[prost(string, tag="2")]
     pub net_usdc: ::prost::alloc::string::String,
-    /// share_value from Dune query  
+    /// share_value from Dune query
     #[prost(string, tag="3")]
     pub share_value: ::prost::alloc::string::String,
     /// trading_pnl from Dune query
@@ -87,13 +93,19 @@ pub struct DuneTokenTransfer {
     /// Already converted to USDC units (divided by 1000000)
     #[prost(string, tag="4")]
     pub amount: ::prost::alloc::string::String,
-    /// "ERC1155_SINGLE", "ERC1155_BATCH", "ERC20"
+    /// "ERC1155_SINGLE", "ERC1155_BATCH", "MINT", "BURN", "ERC20", "ERC20_AIRDROP"
     #[prost(string, tag="5")]
     pub transfer_type: ::prost::alloc::string::String,
     #[prost(message, optional, tag="6")]
     pub block_timestamp: ::core::option::Option<::prost_types::Timestamp>,
     #[prost(uint64, tag="7")]
     pub block_number: u64,
+    #[prost(uint32, tag="8")]
+    pub log_index: u32,
+    #[prost(uint32, tag="9")]
+    pub tx_index: u32,
+    #[prost(string, tag="10")]
+    pub tx_from: ::prost::alloc::string::String,
 }
 /// Dune Order Fill - From clob_combined CTE in Dune query
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -125,6 +137,10 @@ pub struct DuneOrderFill {
     pub order_hash: ::prost::alloc::string::String,
     #[prost(bool, tag="13")]
     pub is_neg_risk: bool,
+    #[prost(uint32, tag="14")]
+    pub tx_index: u32,
+    #[prost(string, tag="15")]
+    pub tx_from: ::prost::alloc::string::String,
 }
 /// Dune Reward Claim - From rewards CTE in Dune query
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -151,6 +167,10 @@ pub struct DuneRewardClaim {
     pub usd_amount: ::prost::alloc::string::String,
     #[prost(string, tag="9")]
     pub token_address: ::prost::alloc::string::String,
+    #[prost(uint32, tag="10")]
+    pub tx_index: u32,
+    #[prost(string, tag="11")]
+    pub tx_from: ::prost::alloc::string::String,
 }
 /// Dune Price Data - From prices CTE in Dune query
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -184,6 +204,214 @@ pub struct DuneTokenHolding {
     #[prost(string, tag="5")]
     pub share_value: ::prost::alloc::string::String,
 }
+/// On-Chain Market P&L - Per-condition statistics accumulated from stores
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnChainMarketPnLs {
+    #[prost(message, repeated, tag="1")]
+    pub market_pnls: ::prost::alloc::vec::Vec<OnChainMarketPnL>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnChainMarketPnL {
+    #[prost(string, tag="1")]
+    pub condition_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub question_id: ::prost::alloc::string::String,
+    /// USDC notional of maker fills (divided by 1000000)
+    #[prost(string, tag="3")]
+    pub total_volume: ::prost::alloc::string::String,
+    /// Number of maker fills
+    #[prost(string, tag="4")]
+    pub total_trades: ::prost::alloc::string::String,
+    /// Fees converted to USDC
+    #[prost(string, tag="5")]
+    pub total_fees: ::prost::alloc::string::String,
+    /// Outcome index with the highest payout, comma separated on ties
+    #[prost(string, tag="6")]
+    pub winning_outcome: ::prost::alloc::string::String,
+    /// Payout of the winning outcome (numerator / denominator)
+    #[prost(string, tag="7")]
+    pub resolution_price: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="8")]
+    pub created_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag="9")]
+    pub resolved_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(bool, tag="10")]
+    pub is_neg_risk: bool,
+    #[prost(uint64, tag="12")]
+    pub block_number: u64,
+}
+/// On-Chain Global P&L - Protocol-wide statistics, one running snapshot per block plus one per finished UTC day
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnChainGlobalPnLs {
+    #[prost(message, repeated, tag="1")]
+    pub global_pnls: ::prost::alloc::vec::Vec<OnChainGlobalPnL>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnChainGlobalPnL {
+    /// USDC notional of maker fills (divided by 1000000)
+    #[prost(string, tag="1")]
+    pub total_volume: ::prost::alloc::string::String,
+    /// Number of maker fills
+    #[prost(string, tag="2")]
+    pub total_trades: ::prost::alloc::string::String,
+    /// Fees converted to USDC
+    #[prost(string, tag="3")]
+    pub total_fees: ::prost::alloc::string::String,
+    /// Unique traders of the UTC day (RUNNING: the current day so far)
+    #[prost(string, tag="4")]
+    pub active_users: ::prost::alloc::string::String,
+    /// RUNNING: registered markets not yet resolved, DAY: markets traded that day
+    #[prost(string, tag="5")]
+    pub active_markets: ::prost::alloc::string::String,
+    /// RUNNING: conditions resolved so far, DAY: conditions resolved that day
+    #[prost(string, tag="6")]
+    pub resolved_markets: ::prost::alloc::string::String,
+    /// RUNNING: block time, DAY: start of the UTC day
+    #[prost(message, optional, tag="7")]
+    pub timestamp: ::core::option::Option<::prost_types::Timestamp>,
+    /// "RUNNING" or "DAY"
+    #[prost(string, tag="8")]
+    pub period: ::prost::alloc::string::String,
+    #[prost(uint64, tag="9")]
+    pub block_number: u64,
+    /// Unique traders since the start block
+    #[prost(string, tag="10")]
+    pub total_users: ::prost::alloc::string::String,
+}
+/// Wallet P&L - Net USDC plus open positions at their latest traded price (map_wallet_pnl row, kv_out value for wallet:<addr>)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WalletPnL {
+    #[prost(string, tag="1")]
+    pub wallet: ::prost::alloc::string::String,
+    /// Net USDC from fills, splits, merges and redemptions (divided by 1000000)
+    #[prost(string, tag="2")]
+    pub net_usdc: ::prost::alloc::string::String,
+    /// Outcome tokens held at their latest CLOB price, never traded tokens count 0
+    #[prost(string, tag="3")]
+    pub share_value: ::prost::alloc::string::String,
+    /// net_usdc + share_value
+    #[prost(string, tag="4")]
+    pub total_pnl: ::prost::alloc::string::String,
+    #[prost(uint64, tag="5")]
+    pub block_number: u64,
+    #[prost(message, optional, tag="6")]
+    pub updated_at: ::core::option::Option<::prost_types::Timestamp>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WalletPnLs {
+    #[prost(message, repeated, tag="1")]
+    pub wallet_pnls: ::prost::alloc::vec::Vec<WalletPnL>,
+}
+/// Position Snapshot - Latest outcome token balance (kv_out value for position:<addr>:<token>)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PositionSnapshot {
+    #[prost(string, tag="1")]
+    pub wallet: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub token_id: ::prost::alloc::string::String,
+    /// Outcome tokens held (divided by 1000000)
+    #[prost(string, tag="3")]
+    pub balance: ::prost::alloc::string::String,
+    #[prost(uint64, tag="4")]
+    pub block_number: u64,
+    #[prost(message, optional, tag="5")]
+    pub updated_at: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// Collateral Token - ERC20 backing CTF positions or FPMM markets (store_collateral_tokens value for collateral:<addr>)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CollateralToken {
+    #[prost(bytes="vec", tag="1")]
+    pub address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(uint32, tag="3")]
+    pub decimals: u32,
+    #[prost(uint64, tag="4")]
+    pub first_seen_block: u64,
+    /// "network" (profile entry) or "unknown" (decimals not known, amounts left unvalued)
+    #[prost(string, tag="5")]
+    pub source: ::prost::alloc::string::String,
+}
+/// Enriched Trades - One record per fill with sides, outcome token and USDC amounts resolved
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Trades {
+    #[prost(message, repeated, tag="1")]
+    pub trades: ::prost::alloc::vec::Vec<Trade>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Trade {
+    #[prost(string, tag="1")]
+    pub evt_tx_hash: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    /// "CLOB", "NEG_RISK", "FPMM"
+    #[prost(string, tag="5")]
+    pub venue: ::prost::alloc::string::String,
+    /// CTF Exchange, NegRisk CTF Exchange or FPMM market contract
+    #[prost(string, tag="6")]
+    pub exchange_address: ::prost::alloc::string::String,
+    /// Empty for FPMM trades
+    #[prost(string, tag="7")]
+    pub order_hash: ::prost::alloc::string::String,
+    /// FPMM market contract for FPMM trades
+    #[prost(string, tag="8")]
+    pub maker_address: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub taker_address: ::prost::alloc::string::String,
+    /// "BUY" or "SELL" from the outcome token perspective
+    #[prost(string, tag="10")]
+    pub maker_side: ::prost::alloc::string::String,
+    #[prost(string, tag="11")]
+    pub taker_side: ::prost::alloc::string::String,
+    /// Outcome token position id, empty for FPMM trades
+    #[prost(string, tag="12")]
+    pub token_id: ::prost::alloc::string::String,
+    /// Token position in the registered market pair, or FPMM outcome index
+    #[prost(uint32, tag="13")]
+    pub outcome_index: u32,
+    #[prost(string, tag="14")]
+    pub condition_id: ::prost::alloc::string::String,
+    #[prost(string, tag="15")]
+    pub question_id: ::prost::alloc::string::String,
+    /// USDC per share
+    #[prost(string, tag="16")]
+    pub price: ::prost::alloc::string::String,
+    /// Outcome tokens traded (divided by 1000000)
+    #[prost(string, tag="17")]
+    pub shares: ::prost::alloc::string::String,
+    /// USDC notional (divided by 1000000)
+    #[prost(string, tag="18")]
+    pub usdc_amount: ::prost::alloc::string::String,
+    /// Fee converted to USDC (divided by 1000000)
+    #[prost(string, tag="19")]
+    pub fee_usdc: ::prost::alloc::string::String,
+    /// Taker order fill against the exchange, repeats its maker fills
+    #[prost(bool, tag="20")]
+    pub is_taker_order: bool,
+    /// Collateral decimals used by shares, usdc_amount and fee_usdc
+    #[prost(uint32, tag="21")]
+    pub decimals: u32,
+    /// Position of the log's transaction in the block
+    #[prost(uint32, tag="22")]
+    pub evt_tx_index: u32,
+    /// Sender of the log's transaction
+    #[prost(bytes="vec", tag="23")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
+}
 /// TokenRegistered Events (CTF Exchange)
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -208,6 +436,10 @@ pub struct TokenRegistered {
     pub token0: ::prost::alloc::string::String,
     #[prost(string, tag="7")]
     pub token1: ::prost::alloc::string::String,
+    #[prost(uint32, tag="8")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="9")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 /// NegRisk TokenRegistered Events
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -239,6 +471,10 @@ pub struct NegRiskTokenRegistered {
     pub is_augmented: bool,
     #[prost(string, tag="10")]
     pub event_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag="11")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="12")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 /// OrderFilled Events
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -274,6 +510,82 @@ pub struct OrderFilled {
     pub fee: ::prost::alloc::string::String,
     #[prost(bytes="vec", tag="12")]
     pub order_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="13")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="14")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
+}
+/// OrdersMatched Events - Emitted once per taker order, after the OrderFilled logs it settled
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrdersMatched {
+    #[prost(string, tag="1")]
+    pub evt_tx_hash: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="5")]
+    pub taker_order_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="6")]
+    pub taker_order_maker: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="7")]
+    pub maker_asset_id: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub taker_asset_id: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub maker_amount_filled: ::prost::alloc::string::String,
+    #[prost(string, tag="10")]
+    pub taker_amount_filled: ::prost::alloc::string::String,
+    #[prost(uint32, tag="11")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="12")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
+}
+/// Order Matches - OrdersMatched grouped with the OrderFilled logs of the same match
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderMatches {
+    #[prost(message, repeated, tag="1")]
+    pub matches: ::prost::alloc::vec::Vec<OrderMatch>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderMatch {
+    /// "CLOB" or "NEG_RISK"
+    #[prost(string, tag="1")]
+    pub venue: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub exchange_address: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub orders_matched: ::core::option::Option<OrdersMatched>,
+    /// Taker order fill against the exchange
+    #[prost(message, optional, tag="4")]
+    pub taker_fill: ::core::option::Option<OrderFilled>,
+    /// Maker orders filled by the taker order, in log order
+    #[prost(message, repeated, tag="5")]
+    pub maker_fills: ::prost::alloc::vec::Vec<OrderFilled>,
+    /// "BUY" or "SELL" from the outcome token perspective
+    #[prost(string, tag="6")]
+    pub taker_side: ::prost::alloc::string::String,
+    #[prost(string, tag="7")]
+    pub token_id: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub condition_id: ::prost::alloc::string::String,
+    /// Outcome tokens traded by the taker (divided by 1000000)
+    #[prost(string, tag="9")]
+    pub size: ::prost::alloc::string::String,
+    /// USDC notional (divided by 1000000)
+    #[prost(string, tag="10")]
+    pub usdc_amount: ::prost::alloc::string::String,
+    /// USDC per share across all maker fills
+    #[prost(string, tag="11")]
+    pub average_price: ::prost::alloc::string::String,
+    /// Taker fee converted to USDC (divided by 1000000)
+    #[prost(string, tag="12")]
+    pub fee_usdc: ::prost::alloc::string::String,
 }
 /// Fixed Product Market Maker Factory Events
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -307,6 +619,10 @@ pub struct FpmmFactoryCreation {
     pub end_time: ::prost::alloc::string::String,
     #[prost(string, tag="11")]
     pub question_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag="12")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="13")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 /// ERC1155 TransferSingle Events
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -338,6 +654,10 @@ pub struct Erc1155TransferSingle {
     pub id: ::prost::alloc::string::String,
     #[prost(string, tag="10")]
     pub value: ::prost::alloc::string::String,
+    #[prost(uint32, tag="11")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="12")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 /// ERC1155 TransferBatch Events
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -369,6 +689,10 @@ pub struct Erc1155TransferBatch {
     pub ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(string, repeated, tag="10")]
     pub values: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(uint32, tag="11")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="12")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 /// ERC20 Transfer Events
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -396,6 +720,10 @@ pub struct Erc20Transfer {
     pub to: ::prost::alloc::vec::Vec<u8>,
     #[prost(string, tag="8")]
     pub value: ::prost::alloc::string::String,
+    #[prost(uint32, tag="9")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="10")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 /// Merkle Distributor Events
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -421,6 +749,10 @@ pub struct MerkleDistributorClaimed {
     pub amount: ::prost::alloc::string::String,
     #[prost(string, tag="7")]
     pub token_address: ::prost::alloc::string::String,
+    #[prost(uint32, tag="8")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="9")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 /// CTF Events
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -450,6 +782,21 @@ pub struct UsdcEvents {
     #[prost(message, repeated, tag="2")]
     pub approvals: ::prost::alloc::vec::Vec<UsdcApproval>,
 }
+/// Exchange Admin and Order Lifecycle Events (CTF Exchange and NegRisk CTF Exchange)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExchangeEvents {
+    #[prost(message, repeated, tag="1")]
+    pub order_cancellations: ::prost::alloc::vec::Vec<ExchangeOrderCancelled>,
+    #[prost(message, repeated, tag="2")]
+    pub fees_charged: ::prost::alloc::vec::Vec<ExchangeFeeCharged>,
+    #[prost(message, repeated, tag="3")]
+    pub trading_pauses: ::prost::alloc::vec::Vec<ExchangeTradingPaused>,
+    #[prost(message, repeated, tag="4")]
+    pub role_changes: ::prost::alloc::vec::Vec<ExchangeRoleChange>,
+    #[prost(message, repeated, tag="5")]
+    pub factory_updates: ::prost::alloc::vec::Vec<ExchangeFactoryUpdate>,
+}
 /// CTF Event Messages
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -470,6 +817,10 @@ pub struct CtfConditionPreparation {
     pub question_id: ::prost::alloc::vec::Vec<u8>,
     #[prost(uint32, tag="8")]
     pub outcome_slot_count: u32,
+    #[prost(uint32, tag="9")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="10")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -494,6 +845,10 @@ pub struct CtfConditionResolution {
     pub payout_numerators: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(string, tag="10")]
     pub payout_denominator: ::prost::alloc::string::String,
+    #[prost(uint32, tag="11")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="12")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -518,6 +873,10 @@ pub struct CtfPositionSplit {
     pub partition: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(string, tag="10")]
     pub amount: ::prost::alloc::string::String,
+    #[prost(uint32, tag="11")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="12")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -542,6 +901,10 @@ pub struct CtfPositionMerge {
     pub partition: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(string, tag="10")]
     pub amount: ::prost::alloc::string::String,
+    #[prost(uint32, tag="11")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="12")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -566,6 +929,10 @@ pub struct CtfPositionRedeem {
     pub index_sets: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(string, tag="10")]
     pub payout: ::prost::alloc::string::String,
+    #[prost(uint32, tag="11")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="12")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -588,6 +955,10 @@ pub struct CtfTransferSingle {
     pub token_id: ::prost::alloc::string::String,
     #[prost(string, tag="9")]
     pub value: ::prost::alloc::string::String,
+    #[prost(uint32, tag="10")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="11")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -610,6 +981,10 @@ pub struct CtfTransferBatch {
     pub token_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(string, repeated, tag="9")]
     pub values: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(uint32, tag="10")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="11")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 /// USDC Event Messages
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -629,6 +1004,10 @@ pub struct UsdcTransfer {
     pub to: ::prost::alloc::vec::Vec<u8>,
     #[prost(string, tag="7")]
     pub value: ::prost::alloc::string::String,
+    #[prost(uint32, tag="8")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="9")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -647,5 +1026,194 @@ pub struct UsdcApproval {
     pub spender: ::prost::alloc::vec::Vec<u8>,
     #[prost(string, tag="7")]
     pub value: ::prost::alloc::string::String,
+    #[prost(uint32, tag="8")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="9")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
+}
+/// Exchange Event Messages
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExchangeOrderCancelled {
+    #[prost(string, tag="1")]
+    pub evt_tx_hash: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="5")]
+    pub exchange: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="6")]
+    pub order_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="7")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="8")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExchangeFeeCharged {
+    #[prost(string, tag="1")]
+    pub evt_tx_hash: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="5")]
+    pub exchange: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="6")]
+    pub receiver: ::prost::alloc::vec::Vec<u8>,
+    /// "0" when the fee is charged in USDC
+    #[prost(string, tag="7")]
+    pub token_id: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub amount: ::prost::alloc::string::String,
+    #[prost(uint32, tag="9")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="10")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExchangeTradingPaused {
+    #[prost(string, tag="1")]
+    pub evt_tx_hash: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="5")]
+    pub exchange: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="6")]
+    pub pauser: ::prost::alloc::vec::Vec<u8>,
+    /// false for TradingUnpaused
+    #[prost(bool, tag="7")]
+    pub paused: bool,
+    #[prost(uint32, tag="8")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="9")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExchangeRoleChange {
+    #[prost(string, tag="1")]
+    pub evt_tx_hash: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="5")]
+    pub exchange: ::prost::alloc::vec::Vec<u8>,
+    /// "ADMIN" or "OPERATOR"
+    #[prost(string, tag="6")]
+    pub role: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="7")]
+    pub account: ::prost::alloc::vec::Vec<u8>,
+    /// Admin that made the change
+    #[prost(bytes="vec", tag="8")]
+    pub admin: ::prost::alloc::vec::Vec<u8>,
+    /// true for NewAdmin/NewOperator, false for RemovedAdmin/RemovedOperator
+    #[prost(bool, tag="9")]
+    pub granted: bool,
+    #[prost(uint32, tag="10")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="11")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExchangeFactoryUpdate {
+    #[prost(string, tag="1")]
+    pub evt_tx_hash: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    #[prost(bytes="vec", tag="5")]
+    pub exchange: ::prost::alloc::vec::Vec<u8>,
+    /// "PROXY" or "SAFE"
+    #[prost(string, tag="6")]
+    pub factory: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="7")]
+    pub old_factory: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="8")]
+    pub new_factory: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="9")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="10")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
+}
+/// Per-block decode and filtering counts (map_diagnostics)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockDiagnostics {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(message, optional, tag="2")]
+    pub block_timestamp: ::core::option::Option<::prost_types::Timestamp>,
+    /// Logs that took effect
+    #[prost(uint64, tag="3")]
+    pub logs_seen: u64,
+    /// Logs of failed transactions and reverted calls
+    #[prost(uint64, tag="4")]
+    pub logs_skipped: u64,
+    /// Sorted by event_type
+    #[prost(message, repeated, tag="5")]
+    pub events: ::prost::alloc::vec::Vec<EventDiagnostics>,
+    /// Sorted by address, then topic0
+    #[prost(message, repeated, tag="6")]
+    pub unknown_topics: ::prost::alloc::vec::Vec<UnknownTopic>,
+    #[prost(message, repeated, tag="7")]
+    pub dropped_records: ::prost::alloc::vec::Vec<DroppedRecords>,
+}
+/// Topic-routed events (token transfers, FPMM trades, QuestionInitialized) are counted at every address,
+/// so ERC721 Transfers sharing the ERC20 topic0 show up as failed Transfers
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventDiagnostics {
+    /// Solidity event name, e.g. "OrderFilled"
+    #[prost(string, tag="1")]
+    pub event_type: ::prost::alloc::string::String,
+    #[prost(uint64, tag="2")]
+    pub seen: u64,
+    #[prost(uint64, tag="3")]
+    pub decoded: u64,
+    /// Known topic0 that did not decode (short data, missing topics)
+    #[prost(uint64, tag="4")]
+    pub failed: u64,
+}
+/// Logs of the network's contracts with a topic0 no decoder reads
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnknownTopic {
+    #[prost(bytes="vec", tag="1")]
+    pub address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="2")]
+    pub topic0: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="3")]
+    pub count: u64,
+}
+/// Decoded events a module left out of its output
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DroppedRecords {
+    #[prost(string, tag="1")]
+    pub module: ::prost::alloc::string::String,
+    /// "excluded_address", "usdc_not_trading_related" or "wallet_filter"
+    #[prost(string, tag="2")]
+    pub reason: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub count: u64,
 }
 // @@protoc_insertion_point(module)
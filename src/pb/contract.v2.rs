@@ -0,0 +1,397 @@
+// @generated
+// This file is @generated by prost-build.
+/// Pure Dune Query P&L Data (v1 PureDunePnL)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PureDunePnL {
+    #[prost(message, repeated, tag="1")]
+    pub user_pnls: ::prost::alloc::vec::Vec<UserPnL>,
+    #[prost(message, repeated, tag="2")]
+    pub market_data: ::prost::alloc::vec::Vec<MarketData>,
+    #[prost(message, repeated, tag="3")]
+    pub token_transfers: ::prost::alloc::vec::Vec<TokenTransfer>,
+    #[prost(message, repeated, tag="4")]
+    pub order_fills: ::prost::alloc::vec::Vec<OrderFill>,
+    #[prost(message, repeated, tag="5")]
+    pub reward_claims: ::prost::alloc::vec::Vec<RewardClaim>,
+    #[prost(message, repeated, tag="6")]
+    pub price_data: ::prost::alloc::vec::Vec<PriceData>,
+    #[prost(uint64, tag="7")]
+    pub total_users: u64,
+    #[prost(string, tag="8")]
+    pub total_volume_raw: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub total_profits_raw: ::prost::alloc::string::String,
+    #[prost(string, tag="10")]
+    pub total_losses_raw: ::prost::alloc::string::String,
+    /// Decimals of the total_*_raw amounts (USDC, 6)
+    #[prost(uint32, tag="11")]
+    pub decimals: u32,
+    #[prost(uint64, tag="12")]
+    pub block_number: u64,
+    #[prost(message, optional, tag="13")]
+    pub block_timestamp: ::core::option::Option<::prost_types::Timestamp>,
+    /// Logs of failed transactions and reverted calls left out of this block
+    #[prost(uint64, tag="14")]
+    pub skipped_logs: u64,
+}
+/// P&L amounts are USDC base units, truncated to 6 decimals where prices add digits
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UserPnL {
+    #[prost(bytes="vec", tag="1")]
+    pub user_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub net_usdc_raw: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub share_value_raw: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub trading_pnl_raw: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub liq_pnl_raw: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub total_pnl_raw: ::prost::alloc::string::String,
+    #[prost(uint32, tag="7")]
+    pub decimals: u32,
+    #[prost(message, repeated, tag="8")]
+    pub holdings: ::prost::alloc::vec::Vec<TokenHolding>,
+    #[prost(message, optional, tag="9")]
+    pub last_activity: ::core::option::Option<::prost_types::Timestamp>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenHolding {
+    /// "USDC" for the collateral balance
+    #[prost(string, tag="1")]
+    pub token_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub amount_raw: ::prost::alloc::string::String,
+    /// USDC per share, fixed-point decimal
+    #[prost(string, tag="3")]
+    pub latest_price: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub share_value_raw: ::prost::alloc::string::String,
+    #[prost(uint32, tag="5")]
+    pub decimals: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MarketData {
+    #[prost(bytes="vec", tag="1")]
+    pub condition_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub token0: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub token1: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub question: ::prost::alloc::string::String,
+    #[prost(bool, tag="5")]
+    pub is_neg_risk: bool,
+    #[prost(message, optional, tag="6")]
+    pub created_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="7")]
+    pub block_number: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenTransfer {
+    #[prost(bytes="vec", tag="1")]
+    pub transaction_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="2")]
+    pub user_address: ::prost::alloc::vec::Vec<u8>,
+    /// "USDC" for collateral transfers
+    #[prost(string, tag="3")]
+    pub token_id: ::prost::alloc::string::String,
+    /// Negative for sends
+    #[prost(string, tag="4")]
+    pub amount_raw: ::prost::alloc::string::String,
+    #[prost(uint32, tag="5")]
+    pub decimals: u32,
+    #[prost(enumeration="TransferType", tag="6")]
+    pub transfer_type: i32,
+    #[prost(message, optional, tag="7")]
+    pub block_timestamp: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="8")]
+    pub block_number: u64,
+    #[prost(uint32, tag="9")]
+    pub log_index: u32,
+    #[prost(uint32, tag="10")]
+    pub tx_index: u32,
+    #[prost(bytes="vec", tag="11")]
+    pub tx_from: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderFill {
+    #[prost(bytes="vec", tag="1")]
+    pub transaction_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub log_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub block_timestamp: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub block_number: u64,
+    #[prost(bytes="vec", tag="5")]
+    pub maker_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="6")]
+    pub taker_address: ::prost::alloc::vec::Vec<u8>,
+    /// "0" is USDC
+    #[prost(string, tag="7")]
+    pub maker_asset_id: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub taker_asset_id: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub maker_amount_raw: ::prost::alloc::string::String,
+    #[prost(string, tag="10")]
+    pub taker_amount_raw: ::prost::alloc::string::String,
+    #[prost(string, tag="11")]
+    pub fee_raw: ::prost::alloc::string::String,
+    #[prost(uint32, tag="12")]
+    pub decimals: u32,
+    #[prost(bytes="vec", tag="13")]
+    pub order_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration="Venue", tag="14")]
+    pub venue: i32,
+    #[prost(uint32, tag="15")]
+    pub tx_index: u32,
+    #[prost(bytes="vec", tag="16")]
+    pub tx_from: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RewardClaim {
+    #[prost(bytes="vec", tag="1")]
+    pub transaction_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub log_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub block_timestamp: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub block_number: u64,
+    #[prost(bytes="vec", tag="5")]
+    pub airdrop_recipient: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration="RewardAsset", tag="6")]
+    pub asset: i32,
+    #[prost(string, tag="7")]
+    pub amount_raw: ::prost::alloc::string::String,
+    /// 18 for UMA, 6 for USDC
+    #[prost(uint32, tag="8")]
+    pub decimals: u32,
+    /// Distributor that paid the claim, empty when unknown
+    #[prost(bytes="vec", tag="9")]
+    pub token_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="10")]
+    pub tx_index: u32,
+    #[prost(bytes="vec", tag="11")]
+    pub tx_from: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PriceData {
+    #[prost(string, tag="1")]
+    pub token_id: ::prost::alloc::string::String,
+    /// Fixed-point decimal from the latest trade
+    #[prost(string, tag="2")]
+    pub price: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub last_trade_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub block_number: u64,
+}
+/// Enriched Trades (v1 Trades)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Trades {
+    #[prost(message, repeated, tag="1")]
+    pub trades: ::prost::alloc::vec::Vec<Trade>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Trade {
+    #[prost(bytes="vec", tag="1")]
+    pub evt_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag="2")]
+    pub evt_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub evt_block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub evt_block_number: u64,
+    #[prost(enumeration="Venue", tag="5")]
+    pub venue: i32,
+    #[prost(bytes="vec", tag="6")]
+    pub exchange_address: ::prost::alloc::vec::Vec<u8>,
+    /// Empty for FPMM trades
+    #[prost(bytes="vec", tag="7")]
+    pub order_hash: ::prost::alloc::vec::Vec<u8>,
+    /// FPMM market contract for FPMM trades
+    #[prost(bytes="vec", tag="8")]
+    pub maker_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="9")]
+    pub taker_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration="Side", tag="10")]
+    pub maker_side: i32,
+    #[prost(enumeration="Side", tag="11")]
+    pub taker_side: i32,
+    /// Empty for FPMM trades
+    #[prost(string, tag="12")]
+    pub token_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag="13")]
+    pub outcome_index: u32,
+    #[prost(bytes="vec", tag="14")]
+    pub condition_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="15")]
+    pub question_id: ::prost::alloc::vec::Vec<u8>,
+    /// Collateral per share, fixed-point decimal
+    #[prost(string, tag="16")]
+    pub price: ::prost::alloc::string::String,
+    #[prost(string, tag="17")]
+    pub shares_raw: ::prost::alloc::string::String,
+    #[prost(string, tag="18")]
+    pub collateral_amount_raw: ::prost::alloc::string::String,
+    #[prost(string, tag="19")]
+    pub fee_raw: ::prost::alloc::string::String,
+    /// Collateral decimals, shared by the outcome tokens
+    #[prost(uint32, tag="20")]
+    pub decimals: u32,
+    #[prost(bool, tag="21")]
+    pub is_taker_order: bool,
+    #[prost(uint32, tag="22")]
+    pub evt_tx_index: u32,
+    #[prost(bytes="vec", tag="23")]
+    pub evt_tx_from: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TransferType {
+    Unspecified = 0,
+    Erc1155Single = 1,
+    Erc1155Batch = 2,
+    Erc20 = 3,
+    Erc20Airdrop = 4,
+    /// ERC1155 transfer from the zero address
+    Mint = 5,
+    /// ERC1155 transfer to the zero address
+    Burn = 6,
+}
+impl TransferType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "TRANSFER_TYPE_UNSPECIFIED",
+            Self::Erc1155Single => "TRANSFER_TYPE_ERC1155_SINGLE",
+            Self::Erc1155Batch => "TRANSFER_TYPE_ERC1155_BATCH",
+            Self::Erc20 => "TRANSFER_TYPE_ERC20",
+            Self::Erc20Airdrop => "TRANSFER_TYPE_ERC20_AIRDROP",
+            Self::Mint => "TRANSFER_TYPE_MINT",
+            Self::Burn => "TRANSFER_TYPE_BURN",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "TRANSFER_TYPE_UNSPECIFIED" => Some(Self::Unspecified),
+            "TRANSFER_TYPE_ERC1155_SINGLE" => Some(Self::Erc1155Single),
+            "TRANSFER_TYPE_ERC1155_BATCH" => Some(Self::Erc1155Batch),
+            "TRANSFER_TYPE_ERC20" => Some(Self::Erc20),
+            "TRANSFER_TYPE_ERC20_AIRDROP" => Some(Self::Erc20Airdrop),
+            "TRANSFER_TYPE_MINT" => Some(Self::Mint),
+            "TRANSFER_TYPE_BURN" => Some(Self::Burn),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Venue {
+    Unspecified = 0,
+    Clob = 1,
+    NegRisk = 2,
+    Fpmm = 3,
+}
+impl Venue {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "VENUE_UNSPECIFIED",
+            Self::Clob => "VENUE_CLOB",
+            Self::NegRisk => "VENUE_NEG_RISK",
+            Self::Fpmm => "VENUE_FPMM",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "VENUE_UNSPECIFIED" => Some(Self::Unspecified),
+            "VENUE_CLOB" => Some(Self::Clob),
+            "VENUE_NEG_RISK" => Some(Self::NegRisk),
+            "VENUE_FPMM" => Some(Self::Fpmm),
+            _ => None,
+        }
+    }
+}
+/// From the outcome token perspective
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Side {
+    Unspecified = 0,
+    Buy = 1,
+    Sell = 2,
+}
+impl Side {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "SIDE_UNSPECIFIED",
+            Self::Buy => "SIDE_BUY",
+            Self::Sell => "SIDE_SELL",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SIDE_UNSPECIFIED" => Some(Self::Unspecified),
+            "SIDE_BUY" => Some(Self::Buy),
+            "SIDE_SELL" => Some(Self::Sell),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum RewardAsset {
+    Unspecified = 0,
+    Uma = 1,
+    Usdc = 2,
+}
+impl RewardAsset {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "REWARD_ASSET_UNSPECIFIED",
+            Self::Uma => "REWARD_ASSET_UMA",
+            Self::Usdc => "REWARD_ASSET_USDC",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "REWARD_ASSET_UNSPECIFIED" => Some(Self::Unspecified),
+            "REWARD_ASSET_UMA" => Some(Self::Uma),
+            "REWARD_ASSET_USDC" => Some(Self::Usdc),
+            _ => None,
+        }
+    }
+}
+// @@protoc_insertion_point(module)
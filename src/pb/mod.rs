@@ -5,6 +5,11 @@ pub mod contract {
         include!("contract.v1.rs");
         // @@protoc_insertion_point(contract.v1)
     }
+    // @@protoc_insertion_point(attribute:contract.v2)
+    pub mod v2 {
+        include!("contract.v2.rs");
+        // @@protoc_insertion_point(contract.v2)
+    }
 }
 pub mod sf {
     pub mod ethereum {
@@ -23,4 +28,15 @@ pub mod sf {
             }
         }
     }
+    pub mod substreams {
+        pub mod sink {
+            pub mod kv {
+                // @@protoc_insertion_point(attribute:sf.substreams.sink.kv.v1)
+                pub mod v1 {
+                    include!("sf.substreams.sink.kv.v1.rs");
+                    // @@protoc_insertion_point(sf.substreams.sink.kv.v1)
+                }
+            }
+        }
+    }
 }
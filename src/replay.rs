@@ -0,0 +1,500 @@
+// Host-side replay tests: recorded sf.ethereum.type.v2.Block fixtures run through the module logic, no endpoint needed.
+// Each fixture is a directory under tests/fixtures/ holding
+//   blocks.binpb  - length-delimited Block messages in block order
+//   params        - the module params string, optional (defaults to Polygon)
+//   balances.csv  - wallet,token_id,balance_before,balance_after: the CTF balances before the first fixture block and
+//                   after the last one, checked against the store_positions deltas. For a recorded fixture they are
+//                   balanceOf at those blocks; the hand-built split-transfer-redeem has no chain state and its
+//                   balances are worked out by hand from its own events
+//   dune_pnl.csv  - user_address plus any of net_usdc,share_value,trading_pnl,liq_pnl,total_pnl: the Dune query
+//                   result for sample wallets whose whole history is in the fixture, checked against map_pure_dune_pnl
+use super::*;
+use prost::Message;
+use std::path::{Path, PathBuf};
+
+const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+// Dune computes P&L in doubles, differences up to a cent are rounding
+const DUNE_TOLERANCE: &str = "0.01";
+const DUNE_COLUMNS: [&str; 5] = ["net_usdc", "share_value", "trading_pnl", "liq_pnl", "total_pnl"];
+
+pub struct Fixture {
+    pub name: String,
+    pub dir: PathBuf,
+    pub module_params: String,
+    pub params: params::Params,
+    pub blocks: Vec<eth::Block>,
+}
+
+// Fixtures containing `file`, sorted by name
+pub fn fixtures_with(file: &str) -> Vec<Fixture> {
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(FIXTURES_DIR)
+        .map(|entries| entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect())
+        .unwrap_or_default();
+    dirs.retain(|dir| dir.join(file).is_file());
+    dirs.sort();
+    dirs.into_iter().map(|dir| load_fixture(&dir)).collect()
+}
+
+fn load_fixture(dir: &Path) -> Fixture {
+    let module_params = std::fs::read_to_string(dir.join("params")).unwrap_or_default().trim().to_string();
+    Fixture {
+        name: dir.file_name().unwrap().to_string_lossy().into_owned(),
+        dir: dir.to_path_buf(),
+        params: params::Params::parse(&module_params).expect("invalid fixture params"),
+        module_params,
+        blocks: load_blocks(&dir.join("blocks.binpb")),
+    }
+}
+
+pub fn load_blocks(path: &Path) -> Vec<eth::Block> {
+    let bytes = std::fs::read(path).unwrap_or_else(|err| panic!("reading {}: {}", path.display(), err));
+    let mut buf = bytes.as_slice();
+    let mut blocks = Vec::new();
+    while !buf.is_empty() {
+        blocks.push(eth::Block::decode_length_delimited(&mut buf)
+            .unwrap_or_else(|err| panic!("decoding block {} of {}: {}", blocks.len(), path.display(), err)));
+    }
+    blocks
+}
+
+// Header-first CSV rows as column -> value maps, no quoting
+pub fn read_csv(path: &Path) -> Vec<HashMap<String, String>> {
+    let content = std::fs::read_to_string(path).unwrap_or_else(|err| panic!("reading {}: {}", path.display(), err));
+    let mut lines = content.lines().map(str::trim).filter(|line| !line.is_empty());
+    let header: Vec<String> = lines.next().unwrap_or_default().split(',').map(|column| column.trim().to_string()).collect();
+    lines
+        .map(|line| header.iter().cloned().zip(line.split(',').map(|value| value.trim().to_string())).collect())
+        .collect()
+}
+
+// Keys are lowercase 0x wallets, as written by position_deltas
+fn position_key(wallet: &str, token_id: &str) -> String {
+    format!("0x{}:{}", wallet.trim_start_matches("0x").to_lowercase(), token_id)
+}
+
+#[test]
+fn positions_match_balance_snapshots() {
+    let fixtures = fixtures_with("balances.csv");
+    assert!(!fixtures.is_empty(), "no balance fixtures in {}", FIXTURES_DIR);
+
+    let mut mismatches = Vec::new();
+    for fixture in &fixtures {
+        let snapshot = read_csv(&fixture.dir.join("balances.csv"));
+
+        // store_positions in memory, seeded with the balances before the first block
+        let positions = memory_store::MemoryStoreBigInt::default();
+        for row in &snapshot {
+            let balance: BigInt = row["balance_before"].parse().unwrap();
+            positions.add(0, position_key(&row["wallet"], &row["token_id"]), balance);
+        }
+        for blk in &fixture.blocks {
+            add_positions(&fixture.module_params, &ctf_events(fixture.params.network, blk), &positions).unwrap();
+        }
+
+        for row in &snapshot {
+            let key = position_key(&row["wallet"], &row["token_id"]);
+            let expected: BigInt = row["balance_after"].parse().unwrap();
+            let actual = positions.get_last(&key).unwrap_or_else(BigInt::zero);
+            if actual != expected {
+                mismatches.push(format!("{} {}: snapshot {} store {}", fixture.name, key, expected, actual));
+            }
+        }
+    }
+
+    assert!(mismatches.is_empty(), "position store differs from balances.csv:\n{}", mismatches.join("\n"));
+}
+
+// map_pure_dune_pnl rows are per block: holdings are summed over the fixture, then valued at the last price seen
+fn replay_user_pnls(fixture: &Fixture) -> HashMap<String, contract::DuneUserPnL> {
+    let mut users: HashMap<String, contract::DuneUserPnL> = HashMap::new();
+    let mut prices: HashMap<String, contract::DunePriceData> = HashMap::new();
+
+    for blk in &fixture.blocks {
+        let pnl = pure_dune_pnl(&fixture.params, blk);
+        prices.extend(pnl.price_data.into_iter().map(|price| (price.token_id.clone(), price)));
+
+        for row in pnl.user_pnls {
+            let user = users.entry(wallet_id(&row.user_address)).or_insert_with(|| contract::DuneUserPnL {
+                user_address: row.user_address.clone(),
+                ..Default::default()
+            });
+            for holding in row.holdings {
+                match user.holdings.iter_mut().find(|existing| existing.token_id == holding.token_id) {
+                    Some(existing) => {
+                        existing.amount = (parse_decimal(&existing.amount) + parse_decimal(&holding.amount)).to_string();
+                        existing.latest_price = holding.latest_price;
+                    },
+                    None => user.holdings.push(holding),
+                }
+            }
+            user.last_activity = row.last_activity;
+        }
+    }
+
+    for user in users.values_mut() {
+        calculate_user_pnl(user, &prices);
+    }
+    users
+}
+
+// Lowercase hex without 0x, Dune exports addresses with it
+fn wallet_id(address: &str) -> String {
+    address.trim_start_matches("0x").to_lowercase()
+}
+
+fn dune_column<'a>(user: &'a contract::DuneUserPnL, column: &str) -> &'a str {
+    match column {
+        "net_usdc" => &user.net_usdc,
+        "share_value" => &user.share_value,
+        "trading_pnl" => &user.trading_pnl,
+        "liq_pnl" => &user.liq_pnl,
+        _ => &user.total_pnl,
+    }
+}
+
+#[test]
+#[ignore = "no fixture carries a Dune CSV export yet"]
+fn user_pnls_match_dune_snapshots() {
+    let fixtures = fixtures_with("dune_pnl.csv");
+    assert!(!fixtures.is_empty(), "no Dune fixtures in {}", FIXTURES_DIR);
+    let tolerance = parse_decimal(DUNE_TOLERANCE);
+
+    let mut report = Vec::new();
+    for fixture in &fixtures {
+        let users = replay_user_pnls(fixture);
+
+        for row in read_csv(&fixture.dir.join("dune_pnl.csv")) {
+            let wallet = &row["user_address"];
+            let Some(user) = users.get(&wallet_id(wallet)) else {
+                report.push(format!("{} {}: missing from map_pure_dune_pnl", fixture.name, wallet));
+                continue;
+            };
+
+            let diffs: Vec<String> = DUNE_COLUMNS.iter()
+                .filter_map(|column| row.get(*column).map(|expected| (column, expected)))
+                .filter_map(|(column, expected)| {
+                    let actual = dune_column(user, column);
+                    let diff = parse_decimal(actual) - parse_decimal(expected);
+                    (diff.absolute() > tolerance)
+                        .then(|| format!("  {:<12} dune {:>20} substreams {:>20} diff {}", column, expected, actual, diff))
+                })
+                .collect();
+            if !diffs.is_empty() {
+                report.push(format!("{} {}:\n{}", fixture.name, wallet, diffs.join("\n")));
+            }
+        }
+    }
+
+    assert!(report.is_empty(), "map_pure_dune_pnl differs from Dune beyond {}:\n{}", DUNE_TOLERANCE, report.join("\n"));
+}
+
+#[test]
+fn diagnostics_count_every_log() {
+    let fixtures = fixtures_with("blocks.binpb");
+    assert!(!fixtures.is_empty(), "no fixtures in {}", FIXTURES_DIR);
+
+    // Recorded logs all decode
+    for fixture in &fixtures {
+        for blk in &fixture.blocks {
+            let report = diagnostics::block_diagnostics(fixture.params.network, blk, Vec::new());
+            assert_eq!(report.logs_seen, events::block_logs(blk).count() as u64, "{} block {}", fixture.name, blk.number);
+            assert_eq!(report.logs_skipped, events::skipped_log_count(blk), "{} block {}", fixture.name, blk.number);
+            assert!(report.events.iter().all(|event| event.failed == 0 && event.decoded == event.seen), "{} block {}: {:?}", fixture.name, blk.number, report.events);
+            assert!(report.unknown_topics.is_empty(), "{} block {}: {:?}", fixture.name, blk.number, report.unknown_topics);
+        }
+    }
+
+    // Next to a CTF TransferSingle: the same transfer without data, and a log with a topic0 the CTF has no decoder for
+    let fixture = &fixtures[0];
+    let mut blk = fixture.blocks.iter()
+        .find(|blk| events::block_logs(blk).any(|origin| origin.log.topics[0] == abi::TRANSFER_SINGLE_SIG))
+        .expect("no TransferSingle in fixture")
+        .clone();
+    let transfer = events::block_logs(&blk).find(|origin| origin.log.topics[0] == abi::TRANSFER_SINGLE_SIG).unwrap().log.clone();
+    let truncated = eth::Log { data: Vec::new(), ..transfer.clone() };
+    let unknown = eth::Log { topics: vec![vec![0x11; 32]], ..transfer.clone() };
+    for trace in &mut blk.transaction_traces {
+        let receipt_logs = trace.receipt.iter_mut().map(|receipt| &mut receipt.logs);
+        for logs in receipt_logs.chain(trace.calls.iter_mut().map(|call| &mut call.logs)) {
+            if logs.contains(&transfer) {
+                logs.extend([truncated.clone(), unknown.clone()]);
+            }
+        }
+    }
+
+    // With the receiver excluded, map_pure_dune_pnl drops its side of the transfer
+    let receiver = format!("0x{}", Hex(&transfer.topics[3][12..]));
+    let params = params::Params::parse(&format!("network={}&excluded={}", fixture.params.network.name, receiver)).unwrap();
+    let pnl = pure_dune_pnl(&params, &blk);
+    assert_eq!(pnl.dropped_records, vec![contract::DroppedRecords {
+        module: "map_pure_dune_pnl".to_string(),
+        reason: diagnostics::EXCLUDED_ADDRESS.to_string(),
+        count: 1,
+    }]);
+
+    let report = diagnostics::block_diagnostics(params.network, &blk, pnl.dropped_records);
+    let transfers = report.events.iter().find(|event| event.event_type == "TransferSingle").unwrap();
+    assert_eq!((transfers.seen, transfers.decoded, transfers.failed), (2, 1, 1));
+    assert_eq!(report.unknown_topics, vec![contract::UnknownTopic { address: transfer.address.clone(), topic0: vec![0x11; 32], count: 1 }]);
+    assert_eq!(report.dropped_records, vec![contract::DroppedRecords {
+        module: "map_pure_dune_pnl".to_string(),
+        reason: diagnostics::EXCLUDED_ADDRESS.to_string(),
+        count: 1,
+    }]);
+}
+
+#[test]
+fn offline_runner_outputs_every_module() {
+    let fixtures = fixtures_with("blocks.binpb");
+    assert!(!fixtures.is_empty(), "no fixtures in {}", FIXTURES_DIR);
+
+    for fixture in &fixtures {
+        let mut runner = offline::Runner::new(&fixture.module_params).unwrap();
+        let mut positions: HashMap<String, BigInt> = HashMap::new();
+        for blk in &fixture.blocks {
+            let outputs = runner.run(blk).unwrap();
+            let modules: Vec<&str> = outputs.outputs.iter().map(|output| output.module).collect();
+            assert_eq!(modules, offline::MODULES.map(|(module, _)| module), "{} block {}", fixture.name, blk.number);
+
+            // store_positions deltas carry the running sums of position_deltas
+            for (_, key, value) in position_deltas(&fixture.params, &ctf_events(fixture.params.network, blk)) {
+                let balance = positions.entry(key).or_insert_with(BigInt::zero);
+                *balance = balance.clone() + value;
+            }
+            let mut last_values: HashMap<&str, &[u8]> = HashMap::new();
+            for delta in outputs.store_deltas.iter().filter(|delta| delta.module == "store_positions") {
+                last_values.insert(&delta.key, &delta.new_value);
+            }
+            for (key, value) in last_values {
+                assert_eq!(BigInt::from_store_bytes(value), positions[key], "{} block {} {}", fixture.name, blk.number, key);
+            }
+        }
+    }
+}
+
+// As its blockFilter does, map_pure_dune_pnl is skipped on blocks without activity of the params wallets
+#[test]
+fn offline_runner_applies_the_wallet_activity_filter() {
+    let fixture = fixtures_with("blocks.binpb").into_iter().next().expect("no fixtures");
+    let pnl_outputs = |params: &str| -> Vec<contract::PureDunePnL> {
+        let mut runner = offline::Runner::new(params).unwrap();
+        fixture.blocks.iter()
+            .map(|blk| runner.run(blk).unwrap().outputs.into_iter().find(|output| output.module == "map_pure_dune_pnl").unwrap())
+            .map(|output| contract::PureDunePnL::decode(output.data.as_slice()).unwrap())
+            .collect()
+    };
+
+    let unwatched = pnl_outputs(&format!("network={}&wallets=0x{}", fixture.params.network.name, Hex([0x99; 20])));
+    assert!(unwatched.iter().all(|pnl| *pnl == contract::PureDunePnL::default()));
+    let all_wallets = pnl_outputs(&fixture.module_params);
+    assert!(all_wallets.iter().any(|pnl| !pnl.user_pnls.is_empty()));
+}
+
+#[test]
+fn wallet_pnl_values_positions_at_latest_price() {
+    let params = params::Params::parse("").unwrap();
+    let wallet = vec![0x44; 20];
+    let transfer = |evt_index: u32, token_id: &str, value: &str| contract::CtfTransferSingle {
+        evt_index,
+        from: vec![0; 20],
+        to: wallet.clone(),
+        token_id: token_id.to_string(),
+        value: value.to_string(),
+        ..Default::default()
+    };
+    let ctf_events = contract::CtfEvents {
+        transfer_singles: vec![transfer(1, "7", "10000000"), transfer(2, "8", "5000000")],
+        ..Default::default()
+    };
+    let trade = |evt_index: u32, price: &str| contract::Trade { evt_index, token_id: "7".to_string(), price: price.to_string(), ..Default::default() };
+    let trades = contract::Trades { trades: vec![trade(3, "0.5"), trade(4, "0.6")] };
+
+    let positions = memory_store::MemoryStoreBigInt::default();
+    let wallet_cash = memory_store::MemoryStoreBigInt::default();
+    let wallet_tokens = memory_store::MemoryStoreArray::default();
+    let prices = memory_store::MemoryStoreBigDecimal::default();
+    let collaterals = memory_store::MemoryStoreProto::<contract::CollateralToken>::default();
+    add_positions("", &ctf_events, &positions).unwrap();
+    wallet_cash.add(0, format!("0x{}", Hex(&wallet)), BigInt::from(-4_000_000));
+    append_wallet_tokens(&positions.deltas(), &wallet_tokens);
+    set_token_prices(&trades, &prices);
+
+    // Token 8 has no trade yet and counts 0
+    let fills = contract::OrderFilledEvents::default();
+    let clock = Clock { number: 5, ..Default::default() };
+    let wallet_pnls = wallet_pnls(&params, &clock, (&fills, &fills), &ctf_events, &collaterals, &positions, &wallet_cash, &wallet_tokens, &prices, &collaterals);
+    assert_eq!(wallet_pnls.wallet_pnls.len(), 1);
+    let wallet_pnl = &wallet_pnls.wallet_pnls[0];
+    assert_eq!(wallet_pnl.wallet, format!("0x{}", Hex(&wallet)));
+    assert_eq!(parse_decimal(&wallet_pnl.net_usdc), parse_decimal("-4"));
+    assert_eq!(parse_decimal(&wallet_pnl.share_value), parse_decimal("6"));
+    assert_eq!(parse_decimal(&wallet_pnl.total_pnl), parse_decimal("2"));
+    assert_eq!(wallet_pnl.block_number, 5);
+}
+
+#[test]
+fn split_mints_map_positions_to_collateral() {
+    let stakeholder = vec![0x22; 20];
+    let wrapped_collateral = hex_literal::hex!("3a3bd7bb9528e159577f7c2e685cc81a765002e2").to_vec();
+    let batch = |evt_tx_hash: &str, evt_index: u32, from: Vec<u8>, token_ids: [&str; 2]| contract::CtfTransferBatch {
+        evt_tx_hash: evt_tx_hash.to_string(),
+        evt_index,
+        from,
+        to: stakeholder.clone(),
+        token_ids: token_ids.map(str::to_string).to_vec(),
+        values: vec!["5".to_string(); 2],
+        ..Default::default()
+    };
+    let ctf_events = contract::CtfEvents {
+        transfer_batches: vec![
+            batch("0xaa", 3, vec![0; 20], ["11", "12"]),
+            // Not a mint, and a mint of another transaction
+            batch("0xaa", 4, vec![0x33; 20], ["13", "14"]),
+            batch("0xbb", 5, vec![0; 20], ["15", "16"]),
+        ],
+        position_splits: vec![contract::CtfPositionSplit {
+            evt_tx_hash: "0xaa".to_string(),
+            evt_index: 6,
+            stakeholder: stakeholder.clone(),
+            collateral_token: wrapped_collateral.clone(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    assert_eq!(position_collaterals(&ctf_events), vec![(6, "11", wrapped_collateral.as_slice()), (6, "12", wrapped_collateral.as_slice())]);
+    let registered = collateral::resolve(&network::POLYGON, &wrapped_collateral, 0);
+    assert_eq!((registered.symbol.as_str(), registered.decimals, registered.source.as_str()), ("WCOL", 6, "network"));
+}
+
+#[test]
+fn unknown_collateral_is_left_unvalued() {
+    let params = params::Params::parse("").unwrap();
+    let stakeholder = vec![0x22; 20];
+    let unknown_collateral = vec![0x55; 20];
+    let ctf_events = contract::CtfEvents {
+        transfer_batches: vec![contract::CtfTransferBatch {
+            evt_tx_hash: "0xaa".to_string(),
+            evt_index: 3,
+            from: vec![0; 20],
+            to: stakeholder.clone(),
+            token_ids: vec!["11".to_string(), "12".to_string()],
+            values: vec!["5".to_string(); 2],
+            ..Default::default()
+        }],
+        position_splits: vec![contract::CtfPositionSplit {
+            evt_tx_hash: "0xaa".to_string(),
+            evt_index: 4,
+            stakeholder: stakeholder.clone(),
+            collateral_token: unknown_collateral.clone(),
+            amount: "5".to_string(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let collaterals = memory_store::MemoryStoreProto::<contract::CollateralToken>::default();
+    let position_collaterals = memory_store::MemoryStoreProto::<contract::CollateralToken>::default();
+    set_collateral_tokens("", &ctf_events, &contract::FpmmFactoryEvents::default(), &collaterals).unwrap();
+    set_position_collaterals(&ctf_events, &collaterals, &position_collaterals);
+
+    let registered = collaterals.get_last(collateral::collateral_key(&unknown_collateral)).expect("collateral not registered");
+    assert_eq!((registered.decimals, registered.source.as_str()), (0, collateral::UNKNOWN_SOURCE));
+    assert_eq!(collateral::decimals(&collaterals, &unknown_collateral), None);
+    assert_eq!(collateral::position_decimals(&position_collaterals, "11"), None);
+    // A token whose split was not seen is still valued as USDC
+    assert_eq!(collateral::position_decimals(&position_collaterals, "13"), Some(collateral::USDC_DECIMALS));
+
+    let fills = contract::OrderFilledEvents::default();
+    assert!(wallet_cash_flows(&params, (&fills, &fills), &ctf_events, &collaterals).is_empty());
+}
+
+#[test]
+fn usdc_distributor_claim_is_one_reward_claim() {
+    let network = &network::POLYGON;
+    let recipient = [0x44; 20];
+    let amount = ethabi::encode(&[ethabi::Token::Uint(1_500_000u64.into())]);
+    let word = |address: &[u8]| [&[0u8; 12][..], address].concat();
+    let log = |address: &[u8], topics: Vec<Vec<u8>>, block_index: u32| eth::Log {
+        address: address.to_vec(),
+        topics,
+        data: amount.clone(),
+        block_index,
+        ..Default::default()
+    };
+    // The distributor pays out, then emits Claimed
+    let transfer = log(&network.usdc, vec![abi::ERC20_TRANSFER_SIG.to_vec(), word(&network.usdc_merkle_distributor), word(&recipient)], 0);
+    let claimed = log(&network.usdc_merkle_distributor, vec![abi::CLAIMED_SIG.to_vec(), word(&recipient)], 1);
+    let trace = |hash: u8, logs: Vec<eth::Log>| eth::TransactionTrace {
+        hash: vec![hash; 32],
+        status: eth::TransactionTraceStatus::Succeeded as i32,
+        receipt: Some(eth::TransactionReceipt { logs, ..Default::default() }),
+        ..Default::default()
+    };
+    let blk = eth::Block {
+        number: 1,
+        detail_level: eth::block::DetailLevel::DetaillevelBase as i32,
+        header: Some(eth::BlockHeader { timestamp: Some(prost_types::Timestamp::default()), ..Default::default() }),
+        // A claim, and a distributor transfer without one
+        transaction_traces: vec![trace(0xaa, vec![transfer.clone(), claimed]), trace(0xbb, vec![transfer])],
+        ..Default::default()
+    };
+
+    let params = params::Params::parse("network=polygon").unwrap();
+    let claims: Vec<(String, String)> = pure_dune_pnl(&params, &blk).reward_claims.into_iter()
+        .map(|claim| (claim.transaction_hash, claim.lc_amount))
+        .collect();
+    assert_eq!(claims, vec![(format!("0x{}", Hex([0xaa; 32])), "1.500000".to_string()), (format!("0x{}", Hex([0xbb; 32])), "1.500000".to_string())]);
+}
+
+// A TransferBatch moves holdings like TransferSingle does, and a send with no earlier receive in the block is a
+// negative holding: the block's net change, summed across blocks like any other
+#[test]
+fn batch_transfers_and_early_sends_move_holdings() {
+    let network = &network::POLYGON;
+    let (sender, receiver) = ([0x44; 20], [0x55; 20]);
+    let word = |address: &[u8]| [&[0u8; 12][..], address].concat();
+    let uints = |values: [u64; 2]| ethabi::Token::Array(values.map(|value| ethabi::Token::Uint(value.into())).to_vec());
+    let batch = eth::Log {
+        address: network.ctf.to_vec(),
+        topics: vec![abi::TRANSFER_BATCH_SIG.to_vec(), word(&sender), word(&sender), word(&receiver)],
+        data: ethabi::encode(&[uints([7, 8]), uints([2_000_000, 3_000_000])]),
+        ..Default::default()
+    };
+    let blk = eth::Block {
+        number: 1,
+        detail_level: eth::block::DetailLevel::DetaillevelBase as i32,
+        header: Some(eth::BlockHeader { timestamp: Some(prost_types::Timestamp::default()), ..Default::default() }),
+        transaction_traces: vec![eth::TransactionTrace {
+            hash: vec![0xaa; 32],
+            status: eth::TransactionTraceStatus::Succeeded as i32,
+            receipt: Some(eth::TransactionReceipt { logs: vec![batch], ..Default::default() }),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let params = params::Params::parse("network=polygon").unwrap();
+    let pnl = pure_dune_pnl(&params, &blk);
+    let holdings = |wallet: [u8; 20]| -> Vec<(String, BigDecimal)> {
+        let user = pnl.user_pnls.iter().find(|user| user.user_address == Hex(wallet).to_string()).expect("no user P&L");
+        user.holdings.iter().map(|holding| (holding.token_id.clone(), parse_decimal(&holding.amount))).collect()
+    };
+    assert_eq!(holdings(sender), vec![("7".to_string(), parse_decimal("-2")), ("8".to_string(), parse_decimal("-3"))]);
+    assert_eq!(holdings(receiver), vec![("7".to_string(), parse_decimal("2")), ("8".to_string(), parse_decimal("3"))]);
+}
+
+// Not a Dune comparison: pins map_pure_dune_pnl on the hand-built fixture, whose only mints are TransferBatch
+// splits and whose transfers send shares before the sender's block has any receive
+#[test]
+fn batch_mints_and_sends_move_holdings() {
+    let fixture = fixtures_with("blocks.binpb").into_iter()
+        .find(|fixture| fixture.name == "split-transfer-redeem")
+        .expect("no split-transfer-redeem fixture");
+    let users = replay_user_pnls(&fixture);
+
+    for (wallet, total_pnl) in [("a11ce00000000000000000000000000000000001", "160"), ("b0b0000000000000000000000000000000000002", "30")] {
+        assert_eq!(parse_decimal(&users[wallet].total_pnl), parse_decimal(total_pnl), "{} total_pnl", wallet);
+    }
+}
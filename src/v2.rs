@@ -0,0 +1,187 @@
+// contract.v2 records built from the v1 module outputs: addresses and hashes as bytes, labels as enums,
+// decimal amounts back to integer base units (v1 amounts are exact, so nothing is lost)
+use crate::collateral::USDC_DECIMALS;
+use crate::params;
+use crate::pb::contract::{v1, v2};
+use substreams::scalar::{BigDecimal, BigInt};
+use substreams::Hex;
+
+const UMA_DECIMALS: u32 = 18;
+
+pub fn pure_dune_pnl(pnl: v1::PureDunePnL) -> v2::PureDunePnL {
+    v2::PureDunePnL {
+        user_pnls: pnl.user_pnls.into_iter().map(user_pnl).collect(),
+        market_data: pnl.market_data.into_iter().map(market_data).collect(),
+        token_transfers: pnl.token_transfers.into_iter().map(token_transfer).collect(),
+        order_fills: pnl.order_fills.into_iter().map(order_fill).collect(),
+        reward_claims: pnl.reward_claims.into_iter().map(reward_claim).collect(),
+        price_data: pnl.price_data.into_iter().map(price_data).collect(),
+        total_users: pnl.total_users.parse().unwrap_or_default(),
+        total_volume_raw: raw_amount(&pnl.total_volume, USDC_DECIMALS),
+        total_profits_raw: raw_amount(&pnl.total_profits, USDC_DECIMALS),
+        total_losses_raw: raw_amount(&pnl.total_losses, USDC_DECIMALS),
+        decimals: USDC_DECIMALS,
+        block_number: pnl.block_number,
+        block_timestamp: pnl.block_timestamp,
+        skipped_logs: pnl.skipped_logs,
+    }
+}
+
+pub fn trades(trades: v1::Trades) -> v2::Trades {
+    v2::Trades {
+        trades: trades.trades.into_iter().map(trade).collect(),
+    }
+}
+
+fn user_pnl(user: v1::DuneUserPnL) -> v2::UserPnL {
+    v2::UserPnL {
+        user_address: address(&user.user_address),
+        net_usdc_raw: raw_amount(&user.net_usdc, USDC_DECIMALS),
+        share_value_raw: raw_amount(&user.share_value, USDC_DECIMALS),
+        trading_pnl_raw: raw_amount(&user.trading_pnl, USDC_DECIMALS),
+        liq_pnl_raw: raw_amount(&user.liq_pnl, USDC_DECIMALS),
+        total_pnl_raw: raw_amount(&user.total_pnl, USDC_DECIMALS),
+        decimals: USDC_DECIMALS,
+        holdings: user.holdings.into_iter().map(token_holding).collect(),
+        last_activity: user.last_activity,
+    }
+}
+
+fn token_holding(holding: v1::DuneTokenHolding) -> v2::TokenHolding {
+    v2::TokenHolding {
+        token_id: holding.token_id,
+        amount_raw: raw_amount(&holding.amount, USDC_DECIMALS),
+        latest_price: holding.latest_price,
+        share_value_raw: raw_amount(&holding.share_value, USDC_DECIMALS),
+        decimals: USDC_DECIMALS,
+    }
+}
+
+fn market_data(market: v1::DuneMarketData) -> v2::MarketData {
+    v2::MarketData {
+        condition_id: hex_bytes(&market.condition_id),
+        token0: market.token0,
+        token1: market.token1,
+        question: market.question,
+        is_neg_risk: market.is_neg_risk,
+        created_at: market.created_at,
+        block_number: market.block_number,
+    }
+}
+
+fn token_transfer(transfer: v1::DuneTokenTransfer) -> v2::TokenTransfer {
+    v2::TokenTransfer {
+        transaction_hash: hex_bytes(&transfer.transaction_hash),
+        user_address: address(&transfer.user_address),
+        token_id: transfer.token_id,
+        amount_raw: raw_amount(&transfer.amount, USDC_DECIMALS),
+        decimals: USDC_DECIMALS,
+        transfer_type: v2::TransferType::from_str_name(&format!("TRANSFER_TYPE_{}", transfer.transfer_type))
+            .unwrap_or_default() as i32,
+        block_timestamp: transfer.block_timestamp,
+        block_number: transfer.block_number,
+        log_index: transfer.log_index,
+        tx_index: transfer.tx_index,
+        tx_from: address(&transfer.tx_from),
+    }
+}
+
+// v1 fill amounts are already raw
+fn order_fill(fill: v1::DuneOrderFill) -> v2::OrderFill {
+    v2::OrderFill {
+        transaction_hash: hex_bytes(&fill.transaction_hash),
+        log_index: fill.log_index,
+        block_timestamp: fill.block_timestamp,
+        block_number: fill.block_number,
+        maker_address: address(&fill.maker_address),
+        taker_address: address(&fill.taker_address),
+        maker_asset_id: fill.maker_asset_id,
+        taker_asset_id: fill.taker_asset_id,
+        maker_amount_raw: fill.maker_amount_filled,
+        taker_amount_raw: fill.taker_amount_filled,
+        fee_raw: fill.fee,
+        decimals: USDC_DECIMALS,
+        order_hash: hex_bytes(&fill.order_hash),
+        venue: if fill.is_neg_risk { v2::Venue::NegRisk } else { v2::Venue::Clob } as i32,
+        tx_index: fill.tx_index,
+        tx_from: address(&fill.tx_from),
+    }
+}
+
+fn reward_claim(claim: v1::DuneRewardClaim) -> v2::RewardClaim {
+    let asset = v2::RewardAsset::from_str_name(&format!("REWARD_ASSET_{}", claim.asset.to_uppercase()))
+        .unwrap_or_default();
+    let decimals = if asset == v2::RewardAsset::Uma { UMA_DECIMALS } else { USDC_DECIMALS };
+
+    v2::RewardClaim {
+        transaction_hash: hex_bytes(&claim.transaction_hash),
+        log_index: claim.log_index,
+        block_timestamp: claim.block_timestamp,
+        block_number: claim.block_number,
+        airdrop_recipient: address(&claim.airdrop_recipient),
+        asset: asset as i32,
+        amount_raw: raw_amount(&claim.lc_amount, decimals),
+        decimals,
+        token_address: address(&claim.token_address),
+        tx_index: claim.tx_index,
+        tx_from: address(&claim.tx_from),
+    }
+}
+
+fn price_data(price: v1::DunePriceData) -> v2::PriceData {
+    v2::PriceData {
+        token_id: price.token_id,
+        price: price.price,
+        last_trade_time: price.last_trade_time,
+        block_number: price.block_number,
+    }
+}
+
+fn trade(trade: v1::Trade) -> v2::Trade {
+    v2::Trade {
+        evt_tx_hash: hex_bytes(&trade.evt_tx_hash),
+        evt_index: trade.evt_index,
+        evt_block_time: trade.evt_block_time,
+        evt_block_number: trade.evt_block_number,
+        venue: v2::Venue::from_str_name(&format!("VENUE_{}", trade.venue)).unwrap_or_default() as i32,
+        exchange_address: address(&trade.exchange_address),
+        order_hash: hex_bytes(&trade.order_hash),
+        maker_address: address(&trade.maker_address),
+        taker_address: address(&trade.taker_address),
+        maker_side: side(&trade.maker_side) as i32,
+        taker_side: side(&trade.taker_side) as i32,
+        token_id: trade.token_id,
+        outcome_index: trade.outcome_index,
+        condition_id: hex_bytes(&trade.condition_id),
+        question_id: hex_bytes(&trade.question_id),
+        price: trade.price,
+        shares_raw: raw_amount(&trade.shares, trade.decimals),
+        collateral_amount_raw: raw_amount(&trade.usdc_amount, trade.decimals),
+        fee_raw: raw_amount(&trade.fee_usdc, trade.decimals),
+        decimals: trade.decimals,
+        is_taker_order: trade.is_taker_order,
+        evt_tx_index: trade.evt_tx_index,
+        evt_tx_from: trade.evt_tx_from,
+    }
+}
+
+fn side(label: &str) -> v2::Side {
+    v2::Side::from_str_name(&format!("SIDE_{}", label)).unwrap_or_default()
+}
+
+// Decimal string to integer base units, digits beyond `decimals` are dropped
+fn raw_amount(value: &str, decimals: u32) -> String {
+    let value: BigDecimal = value.parse().unwrap_or_default();
+    let scale = BigDecimal::from(BigInt::from(10u64).pow(decimals));
+    (value * scale).to_bigint().to_string()
+}
+
+// Hex with or without 0x, empty when malformed or a placeholder
+fn hex_bytes(value: &str) -> Vec<u8> {
+    Hex::decode(value.trim_start_matches("0x")).unwrap_or_default()
+}
+
+// 20-byte address from a plain address or a 32-byte topic, empty when malformed
+fn address(value: &str) -> Vec<u8> {
+    params::hex_address(value).map(|address| address.to_vec()).unwrap_or_default()
+}
@@ -15,6 +15,8 @@ echo ""
 
 # Run the Substreams and capture data
 substreams run substreams.yaml map_pure_dune_pnl \
+    -p index_wallet_activity="wallets=$WALLET" \
+    -p map_pure_dune_pnl="wallets=$WALLET" \
     --start-block $START_BLOCK \
     --stop-block $END_BLOCK \
     --substreams-endpoint polygon.streamingfast.io:443 | \
@@ -30,8 +32,8 @@ jq --arg wallet "$WALLET" '
 
 echo ""
 echo "🔍 To track this wallet in real-time:"
-echo "1. Run: substreams run substreams.yaml map_pure_dune_pnl --start-block [BLOCK] --stop-block +1"
-echo "2. Filter for your wallet address in the output"
+echo "1. Run: substreams run substreams.yaml map_pure_dune_pnl -p index_wallet_activity=wallets=$WALLET -p map_pure_dune_pnl=wallets=$WALLET --start-block [BLOCK] --stop-block +1"
+echo "2. Blocks without activity from the wallet are skipped and the output only contains its events"
 echo "3. The Substreams will capture all their Polymarket activity"
 echo ""
 echo "💡 The wallet may not appear in these specific blocks if they haven't been active recently."
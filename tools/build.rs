@@ -0,0 +1,24 @@
+// Native copies of the module and block messages with protobuf JSON (pbjson) serde impls
+use std::path::PathBuf;
+
+const PROTOS: [&str; 3] = ["contract.proto", "contract_v2.proto", "sf/ethereum/type/v2/type.proto"];
+
+fn main() -> anyhow::Result<()> {
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR")?);
+    let proto_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../proto");
+    let descriptors = out_dir.join("descriptors.binpb");
+    for proto in PROTOS {
+        println!("cargo:rerun-if-changed={}", proto_dir.join(proto).display());
+    }
+
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    prost_build::Config::new()
+        .compile_well_known_types()
+        .file_descriptor_set_path(&descriptors)
+        .compile_protos(&PROTOS.map(|proto| proto_dir.join(proto)), &[proto_dir.clone(), protoc_bin_vendored::include_path()?])?;
+
+    pbjson_build::Builder::new()
+        .register_descriptors(&std::fs::read(&descriptors)?)?
+        .build(&[".contract", ".sf.ethereum.type.v2", ".google.protobuf"])?;
+    Ok(())
+}
@@ -0,0 +1,70 @@
+// Fixture capture: prunes a Firehose block dump down to the traces of chosen txs or wallets and writes them
+// as length-delimited Blocks, the blocks.binpb format of tests/fixtures.
+//
+//   cargo run -p polymarket-tools --bin capture_fixture -- --out tests/fixtures/<name>/blocks.binpb \
+//       [--tx 0x<hash>,...] [--wallet 0x<address>,...] <dump>...
+//
+// Dumps are merged-blocks files (.dbin or .dbin.zst), .json Blocks or length-delimited Blocks. Blocks without
+// a matching trace are left out.
+use anyhow::{bail, ensure, Context, Result};
+use polymarket_tools::prune::{prune_block, Filter};
+use polymarket_tools::{read_blocks, write_blocks};
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: capture_fixture --out <blocks.binpb> [--tx 0x<hash>,...] [--wallet 0x<address>,...] <dump>...";
+
+fn main() -> Result<()> {
+    let mut out = None;
+    let mut dumps = Vec::new();
+    let mut filter = Filter::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" | "-o" => out = Some(PathBuf::from(args.next().context(USAGE)?)),
+            "--tx" => {
+                for hash in args.next().context(USAGE)?.split(',') {
+                    let hash = decode_hex(hash)?;
+                    ensure!(hash.len() == 32, "tx hash must be 32 bytes");
+                    filter.tx_hashes.push(hash);
+                }
+            },
+            "--wallet" => {
+                for wallet in args.next().context(USAGE)?.split(',') {
+                    let wallet = decode_hex(wallet)?;
+                    filter.wallets.push(wallet.as_slice().try_into().context("wallet must be 20 bytes")?);
+                }
+            },
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return Ok(());
+            },
+            _ if !arg.starts_with('-') => dumps.push(PathBuf::from(arg)),
+            _ => bail!("unexpected argument {}\n{}", arg, USAGE),
+        }
+    }
+    let out = out.context(USAGE)?;
+    ensure!(!dumps.is_empty(), "{}", USAGE);
+    ensure!(!filter.tx_hashes.is_empty() || !filter.wallets.is_empty(), "nothing to capture, pass --tx or --wallet");
+
+    let mut read = 0;
+    let mut blocks = Vec::new();
+    for dump in &dumps {
+        for blk in read_blocks(dump)? {
+            read += 1;
+            blocks.extend(prune_block(&blk, &filter));
+        }
+    }
+    blocks.sort_by_key(|blk| blk.number);
+    blocks.dedup_by_key(|blk| blk.number);
+
+    write_blocks(&out, &blocks)?;
+    let traces: usize = blocks.iter().map(|blk| blk.transaction_traces.len()).sum();
+    eprintln!("{}: {} traces in {} of {} blocks", out.display(), traces, blocks.len(), read);
+    Ok(())
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>> {
+    let hex = value.trim().trim_start_matches("0x");
+    ensure!(hex.len().is_multiple_of(2) && hex.bytes().all(|byte| byte.is_ascii_hexdigit()), "invalid hex {}", value);
+    Ok((0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect())
+}
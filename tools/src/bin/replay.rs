@@ -0,0 +1,69 @@
+// Offline block replay: runs the P&L module graph on a local block file and prints one JSON line per block.
+//
+//   cargo run -p polymarket-tools --bin replay -- <blocks.binpb|blocks.json> [--params <module params>]
+//
+// Stores live in memory for the whole run, so the file should start where the P&L history of interest starts.
+// Of the blockFilters, only map_pure_dune_pnl's wallet_activity one is applied (see polymarket::offline); the
+// evt_addr/evt_sig filters are not evaluated, which gives the same outputs.
+use anyhow::{bail, Context, Result};
+use polymarket::offline::Runner;
+use polymarket_tools::{output_json, read_blocks, store_value_json};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: replay <blocks.binpb|blocks.json> [--params <module params>]\n\
+    map_pure_dune_pnl is skipped on blocks without wallet_activity, as its blockFilter does; the evt_addr/evt_sig\n\
+    blockFilters of the other modules are not evaluated, their modules give the same output either way";
+
+fn main() -> Result<()> {
+    let mut path = None;
+    let mut params = String::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--params" | "-p" => params = args.next().context(USAGE)?,
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return Ok(());
+            },
+            _ if path.is_none() && !arg.starts_with('-') => path = Some(PathBuf::from(arg)),
+            _ => bail!("unexpected argument {}\n{}", arg, USAGE),
+        }
+    }
+    let path = path.context(USAGE)?;
+
+    let mut runner = Runner::new(&params)?;
+    for blk in read_blocks(&path)? {
+        let outputs = runner.run(&blk).with_context(|| format!("block {}", blk.number))?;
+
+        let mut modules = Map::new();
+        for output in &outputs.outputs {
+            let value = output_json(output.type_name, &output.data).with_context(|| format!("block {} {}", blk.number, output.module))?;
+            modules.insert(output.module.to_string(), value);
+        }
+
+        let mut stores: BTreeMap<&str, Vec<Value>> = BTreeMap::new();
+        for delta in &outputs.store_deltas {
+            let value = |data: &[u8]| store_value_json(delta.value_type, data).with_context(|| format!("block {} {} {}", blk.number, delta.module, delta.key));
+            stores.entry(delta.module).or_default().push(json!({
+                "ordinal": delta.ordinal,
+                "key": delta.key,
+                "oldValue": value(&delta.old_value)?,
+                "newValue": value(&delta.new_value)?,
+            }));
+        }
+
+        println!("{}", json!({
+            "block": blk.number,
+            "hash": hex(&blk.hash),
+            "outputs": modules,
+            "storeDeltas": stores,
+        }));
+    }
+    Ok(())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
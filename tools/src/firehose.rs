@@ -0,0 +1,139 @@
+// Firehose merged-blocks files (dbin, optionally .zst): a header, then u32 big-endian length-prefixed
+// sf.bstream.v1.Block messages whose payload is the sf.ethereum.type.v2.Block
+use anyhow::{bail, ensure, Context, Result};
+use polymarket::offline::Block;
+use prost::encoding::{decode_key, decode_varint, skip_field, DecodeContext, WireType};
+use prost::Message;
+
+const MAGIC: &[u8] = b"dbin";
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+const BLOCK_TYPE_URL: &str = "type.googleapis.com/sf.ethereum.type.v2.Block";
+
+// sf.bstream.v1.Block payload fields: the raw payload_buffer of older files and the Any payload of newer ones
+const PAYLOAD_BUFFER_TAG: u32 = 8;
+const PAYLOAD_ANY_TAGS: [u32; 2] = [10, 11];
+
+pub fn is_zstd(bytes: &[u8]) -> bool {
+    bytes.starts_with(ZSTD_MAGIC)
+}
+
+pub fn is_dbin(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+pub fn decompress(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut decoder = ruzstd::StreamingDecoder::new(bytes).context("zstd frame")?;
+    let mut out = Vec::new();
+    std::io::Read::read_to_end(&mut decoder, &mut out)?;
+    Ok(out)
+}
+
+pub fn dbin_blocks(bytes: &[u8]) -> Result<Vec<Block>> {
+    let mut buf = skip_header(bytes)?;
+    let mut blocks = Vec::new();
+    while !buf.is_empty() {
+        ensure!(buf.len() >= 4, "truncated message length after block {}", blocks.len());
+        let (length, rest) = buf.split_at(4);
+        let length = u32::from_be_bytes(length.try_into()?) as usize;
+        ensure!(rest.len() >= length, "truncated message {}", blocks.len());
+        let (message, rest) = rest.split_at(length);
+        blocks.push(bstream_payload(message).with_context(|| format!("message {}", blocks.len()))?);
+        buf = rest;
+    }
+    Ok(blocks)
+}
+
+// v0: version 0, 3-byte content type, 2-byte content version; v1: version 1, u16 length-prefixed content type
+fn skip_header(bytes: &[u8]) -> Result<&[u8]> {
+    ensure!(is_dbin(bytes), "not a dbin file");
+    let rest = &bytes[MAGIC.len()..];
+    let header_len = match rest.first() {
+        Some(0) => 1 + 3 + 2,
+        Some(1) if rest.len() >= 3 => 1 + 2 + u16::from_be_bytes([rest[1], rest[2]]) as usize,
+        Some(version) => bail!("unsupported dbin version {}", version),
+        None => bail!("truncated dbin header"),
+    };
+    ensure!(rest.len() >= header_len, "truncated dbin header");
+    Ok(&rest[header_len..])
+}
+
+fn bstream_payload(mut buf: &[u8]) -> Result<Block> {
+    while !buf.is_empty() {
+        let (tag, wire_type) = decode_key(&mut buf)?;
+        if wire_type == WireType::LengthDelimited && (tag == PAYLOAD_BUFFER_TAG || PAYLOAD_ANY_TAGS.contains(&tag)) {
+            let length = decode_varint(&mut buf)? as usize;
+            ensure!(buf.len() >= length, "truncated payload");
+            let payload = &buf[..length];
+            if tag == PAYLOAD_BUFFER_TAG {
+                return Ok(Block::decode(payload)?);
+            }
+            let any = Any::decode(payload)?;
+            if any.type_url == BLOCK_TYPE_URL {
+                return Ok(Block::decode(any.value.as_slice())?);
+            }
+            bail!("payload is {}, not an Ethereum block", any.type_url);
+        }
+        skip_field(wire_type, tag, &mut buf, DecodeContext::default())?;
+    }
+    bail!("no block payload")
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Any {
+    #[prost(string, tag = "1")]
+    type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    value: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::encoding::{encode_key, encode_varint};
+
+    fn block(number: u64) -> Block {
+        Block { number, hash: vec![number as u8; 32], ..Default::default() }
+    }
+
+    // sf.bstream.v1.Block with `number` and the payload in field `tag`
+    fn bstream(number: u64, tag: u32, payload: &[u8]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode_key(1, WireType::Varint, &mut buf);
+        encode_varint(number, &mut buf);
+        encode_key(tag, WireType::LengthDelimited, &mut buf);
+        encode_varint(payload.len() as u64, &mut buf);
+        buf.extend_from_slice(payload);
+        buf
+    }
+
+    fn dbin(header: &[u8], messages: &[Vec<u8>]) -> Vec<u8> {
+        let mut buf = header.to_vec();
+        for message in messages {
+            buf.extend_from_slice(&(message.len() as u32).to_be_bytes());
+            buf.extend_from_slice(message);
+        }
+        buf
+    }
+
+    #[test]
+    fn dbin_payloads_decode() {
+        let any = Any { type_url: BLOCK_TYPE_URL.to_string(), value: block(2).encode_to_vec() };
+        let messages = [bstream(1, PAYLOAD_BUFFER_TAG, &block(1).encode_to_vec()), bstream(2, 11, &any.encode_to_vec())];
+
+        let type_url = b"type.googleapis.com/sf.bstream.v1.Block";
+        let mut v1 = b"dbin\x01".to_vec();
+        v1.extend_from_slice(&(type_url.len() as u16).to_be_bytes());
+        v1.extend_from_slice(type_url);
+
+        for header in [b"dbin\x00ETH00".to_vec(), v1] {
+            assert_eq!(dbin_blocks(&dbin(&header, &messages)).unwrap(), vec![block(1), block(2)]);
+        }
+    }
+
+    #[test]
+    fn truncated_dbin_errors() {
+        let file = dbin(b"dbin\x00ETH00", &[bstream(1, PAYLOAD_BUFFER_TAG, &block(1).encode_to_vec())]);
+        assert!(dbin_blocks(&file[..file.len() - 1]).is_err());
+        assert!(dbin_blocks(b"dbin\x07").is_err());
+    }
+}
@@ -0,0 +1,149 @@
+// Host-side tools for working with recorded blocks: block files in and out, module outputs as protobuf JSON
+use anyhow::{bail, Context, Result};
+use polymarket::offline::Block;
+use prost::Message;
+use std::path::Path;
+
+mod firehose;
+pub mod prune;
+
+// Native copies of the proto/ messages with pbjson serde impls, for reading and printing JSON only
+#[allow(clippy::all)]
+pub mod pb {
+    pub mod contract {
+        pub mod v1 {
+            include!(concat!(env!("OUT_DIR"), "/contract.v1.rs"));
+            include!(concat!(env!("OUT_DIR"), "/contract.v1.serde.rs"));
+        }
+        pub mod v2 {
+            include!(concat!(env!("OUT_DIR"), "/contract.v2.rs"));
+            include!(concat!(env!("OUT_DIR"), "/contract.v2.serde.rs"));
+        }
+    }
+    pub mod google {
+        pub mod protobuf {
+            include!(concat!(env!("OUT_DIR"), "/google.protobuf.rs"));
+            include!(concat!(env!("OUT_DIR"), "/google.protobuf.serde.rs"));
+        }
+    }
+    pub mod sf {
+        pub mod ethereum {
+            pub mod r#type {
+                pub mod v2 {
+                    include!(concat!(env!("OUT_DIR"), "/sf.ethereum.r#type.v2.rs"));
+                    include!(concat!(env!("OUT_DIR"), "/sf.ethereum.r#type.v2.serde.rs"));
+                }
+            }
+        }
+    }
+}
+
+// Blocks of a Firehose merged-blocks file (dbin, plain or zstd compressed), of a .json file (one Block object,
+// an array of them or one per line, protobuf JSON mapping), else of length-delimited Block messages like
+// tests/fixtures/*/blocks.binpb
+pub fn read_blocks(path: &Path) -> Result<Vec<Block>> {
+    let mut bytes = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    if firehose::is_zstd(&bytes) {
+        bytes = firehose::decompress(&bytes).with_context(|| format!("decompressing {}", path.display()))?;
+    }
+    let blocks = if firehose::is_dbin(&bytes) {
+        firehose::dbin_blocks(&bytes)
+    } else if path.extension().is_some_and(|extension| extension == "json" || extension == "jsonl") {
+        json_blocks(&bytes)
+    } else {
+        binpb_blocks(&bytes)
+    };
+    blocks.with_context(|| format!("decoding {}", path.display()))
+}
+
+// Length-delimited, the format read_blocks and the fixture tests expect
+pub fn write_blocks(path: &Path, blocks: &[Block]) -> Result<()> {
+    let mut buf = Vec::new();
+    for blk in blocks {
+        blk.encode_length_delimited(&mut buf)?;
+    }
+    std::fs::write(path, buf).with_context(|| format!("writing {}", path.display()))
+}
+
+fn binpb_blocks(mut buf: &[u8]) -> Result<Vec<Block>> {
+    let mut blocks = Vec::new();
+    while !buf.is_empty() {
+        blocks.push(Block::decode_length_delimited(&mut buf).with_context(|| format!("block {}", blocks.len()))?);
+    }
+    Ok(blocks)
+}
+
+fn json_blocks(bytes: &[u8]) -> Result<Vec<Block>> {
+    let mut blocks = Vec::new();
+    for value in serde_json::Deserializer::from_slice(bytes).into_iter::<serde_json::Value>() {
+        match value? {
+            serde_json::Value::Array(values) => blocks.extend(values),
+            value => blocks.push(value),
+        }
+    }
+    blocks.into_iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let block: pb::sf::ethereum::r#type::v2::Block = serde_json::from_value(value).with_context(|| format!("block {}", index))?;
+            Ok(Block::decode(block.encode_to_vec().as_slice())?)
+        })
+        .collect()
+}
+
+// A module output, or a proto store value, as protobuf JSON, `type_name` as in polymarket::offline::MODULES and STORES
+pub fn output_json(type_name: &str, data: &[u8]) -> Result<serde_json::Value> {
+    use pb::contract::{v1, v2};
+    Ok(match type_name {
+        "contract.v1.TokenRegisteredEvents" => serde_json::to_value(v1::TokenRegisteredEvents::decode(data)?)?,
+        "contract.v1.NegRiskTokenRegisteredEvents" => serde_json::to_value(v1::NegRiskTokenRegisteredEvents::decode(data)?)?,
+        "contract.v1.FpmmFactoryEvents" => serde_json::to_value(v1::FpmmFactoryEvents::decode(data)?)?,
+        "contract.v1.OrderFilledEvents" => serde_json::to_value(v1::OrderFilledEvents::decode(data)?)?,
+        "contract.v1.CtfEvents" => serde_json::to_value(v1::CtfEvents::decode(data)?)?,
+        "contract.v1.OnChainMarketPnLs" => serde_json::to_value(v1::OnChainMarketPnLs::decode(data)?)?,
+        "contract.v1.OnChainGlobalPnLs" => serde_json::to_value(v1::OnChainGlobalPnLs::decode(data)?)?,
+        "contract.v1.Trades" => serde_json::to_value(v1::Trades::decode(data)?)?,
+        "contract.v1.OrderMatches" => serde_json::to_value(v1::OrderMatches::decode(data)?)?,
+        "contract.v1.ExchangeEvents" => serde_json::to_value(v1::ExchangeEvents::decode(data)?)?,
+        "contract.v1.WalletPnLs" => serde_json::to_value(v1::WalletPnLs::decode(data)?)?,
+        "contract.v1.PureDunePnL" => serde_json::to_value(v1::PureDunePnL::decode(data)?)?,
+        "contract.v2.PureDunePnL" => serde_json::to_value(v2::PureDunePnL::decode(data)?)?,
+        "contract.v2.Trades" => serde_json::to_value(v2::Trades::decode(data)?)?,
+        "contract.v1.BlockDiagnostics" => serde_json::to_value(v1::BlockDiagnostics::decode(data)?)?,
+        "contract.v1.DuneMarketData" => serde_json::to_value(v1::DuneMarketData::decode(data)?)?,
+        "contract.v1.CtfConditionResolution" => serde_json::to_value(v1::CtfConditionResolution::decode(data)?)?,
+        "contract.v1.FpmmFactoryCreation" => serde_json::to_value(v1::FpmmFactoryCreation::decode(data)?)?,
+        "contract.v1.CollateralToken" => serde_json::to_value(v1::CollateralToken::decode(data)?)?,
+        _ => bail!("no JSON mapping for {}", type_name),
+    })
+}
+
+// A store delta value: bigint, bigdecimal and int64 values are decimal strings already, string values (append stores
+// too) are kept as is, a missing value is null
+pub fn store_value_json(value_type: &str, data: &[u8]) -> Result<serde_json::Value> {
+    Ok(match value_type {
+        _ if data.is_empty() => serde_json::Value::Null,
+        "bigint" | "bigdecimal" | "int64" | "string" => serde_json::Value::String(std::str::from_utf8(data)?.to_string()),
+        _ => output_json(value_type, data)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../tests/fixtures/split-transfer-redeem/blocks.binpb");
+
+    #[test]
+    fn json_blocks_match_binpb() {
+        let blocks = read_blocks(Path::new(FIXTURE)).unwrap();
+        assert!(!blocks.is_empty());
+
+        let json: Vec<serde_json::Value> = blocks.iter()
+            .map(|blk| serde_json::to_value(pb::sf::ethereum::r#type::v2::Block::decode(blk.encode_to_vec().as_slice()).unwrap()).unwrap())
+            .collect();
+        // an array, and one object per line
+        assert_eq!(json_blocks(serde_json::to_string(&json).unwrap().as_bytes()).unwrap(), blocks);
+        let lines: Vec<String> = json.iter().map(|value| value.to_string()).collect();
+        assert_eq!(json_blocks(lines.join("\n").as_bytes()).unwrap(), blocks);
+    }
+}
@@ -0,0 +1,121 @@
+// Fixture pruning: keeps the transaction traces of the chosen txs or touching the chosen wallets, nothing else.
+// Block hash, number, header (timestamp included) and the index, block_index and ordinal of every kept log are
+// left as recorded, so the modules see the kept logs exactly where the full block has them.
+use polymarket::offline::{eth, Block};
+
+#[derive(Default)]
+pub struct Filter {
+    pub tx_hashes: Vec<Vec<u8>>,
+    pub wallets: Vec<[u8; 20]>,
+}
+
+impl Filter {
+    // A wallet matches as tx sender or recipient, call caller or callee, or as an address word of a log's
+    // topics or data (indexed and non-indexed event parameters)
+    pub fn matches(&self, trace: &eth::TransactionTrace) -> bool {
+        if self.tx_hashes.contains(&trace.hash) {
+            return true;
+        }
+        self.wallets.iter().any(|wallet| {
+            trace.from == wallet || trace.to == wallet
+                || trace.calls.iter().any(|call| call.caller == wallet || call.address == wallet)
+                || trace_logs(trace).any(|log| log_mentions(log, wallet))
+        })
+    }
+}
+
+// The block with only the matching traces, None when there are none. Kept calls lose their state, balance,
+// nonce, code and gas changes, which no module reads.
+pub fn prune_block(blk: &Block, filter: &Filter) -> Option<Block> {
+    let transaction_traces: Vec<eth::TransactionTrace> = blk.transaction_traces.iter()
+        .filter(|trace| filter.matches(trace))
+        .cloned()
+        .map(|mut trace| {
+            trace.calls.iter_mut().for_each(prune_call);
+            trace
+        })
+        .collect();
+    if transaction_traces.is_empty() {
+        return None;
+    }
+
+    Some(Block {
+        transaction_traces,
+        uncles: Vec::new(),
+        balance_changes: Vec::new(),
+        code_changes: Vec::new(),
+        system_calls: Vec::new(),
+        ..blk.clone()
+    })
+}
+
+// account_creations is deprecated upstream but still filled on older blocks
+#[allow(deprecated)]
+fn prune_call(call: &mut eth::Call) {
+    call.keccak_preimages.clear();
+    call.storage_changes.clear();
+    call.balance_changes.clear();
+    call.nonce_changes.clear();
+    call.code_changes.clear();
+    call.gas_changes.clear();
+    call.account_creations.clear();
+}
+
+fn trace_logs(trace: &eth::TransactionTrace) -> impl Iterator<Item = &eth::Log> {
+    let receipt_logs = trace.receipt.iter().flat_map(|receipt| receipt.logs.iter());
+    receipt_logs.chain(trace.calls.iter().flat_map(|call| call.logs.iter()))
+}
+
+fn log_mentions(log: &eth::Log, wallet: &[u8; 20]) -> bool {
+    log.topics.iter().skip(1).map(Vec::as_slice).chain(log.data.chunks(32)).any(|word| is_address_word(word, wallet))
+}
+
+// An ABI-encoded address: 12 zero bytes, then the address
+fn is_address_word(word: &[u8], wallet: &[u8; 20]) -> bool {
+    word.len() == 32 && word[..12].iter().all(|byte| *byte == 0) && word[12..] == wallet[..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_blocks;
+    use std::path::Path;
+
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../tests/fixtures/split-transfer-redeem/blocks.binpb");
+    // 0xb0b0000000000000000000000000000000000002, the fixture's receiver
+    const BOB: [u8; 20] = [0xb0, 0xb0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x02];
+
+    fn logs(blk: &Block) -> Vec<(u32, u32, u64)> {
+        blk.transaction_traces.iter()
+            .flat_map(trace_logs)
+            .map(|log| (log.index, log.block_index, log.ordinal))
+            .collect()
+    }
+
+    #[test]
+    fn wallet_filter_keeps_matching_traces_as_recorded() {
+        let blocks = read_blocks(Path::new(FIXTURE)).unwrap();
+        let filter = Filter { wallets: vec![BOB], ..Default::default() };
+        let pruned: Vec<Block> = blocks.iter().filter_map(|blk| prune_block(blk, &filter)).collect();
+
+        // bob first appears in the transfer of the second block
+        assert_eq!(pruned.iter().map(|blk| blk.number).collect::<Vec<_>>(), blocks[1..].iter().map(|blk| blk.number).collect::<Vec<_>>());
+        for blk in &pruned {
+            let full = blocks.iter().find(|full| full.number == blk.number).unwrap();
+            assert_eq!((&blk.hash, &blk.header), (&full.hash, &full.header));
+            assert!(blk.transaction_traces.iter().all(|trace| filter.matches(trace)));
+            assert!(logs(blk).iter().all(|log| logs(full).contains(log)));
+        }
+    }
+
+    #[test]
+    fn tx_filter_keeps_only_that_tx() {
+        let blocks = read_blocks(Path::new(FIXTURE)).unwrap();
+        let hash = blocks[1].transaction_traces[0].hash.clone();
+        let filter = Filter { tx_hashes: vec![hash.clone()], ..Default::default() };
+
+        let pruned: Vec<Block> = blocks.iter().filter_map(|blk| prune_block(blk, &filter)).collect();
+        assert_eq!(pruned.len(), 1);
+        assert_eq!(pruned[0].transaction_traces.iter().map(|trace| &trace.hash).collect::<Vec<_>>(), vec![&hash]);
+    }
+}
@@ -19,6 +19,8 @@ echo ""
 
 # Run Substreams and filter for the specific user
 substreams run substreams.yaml map_pure_dune_pnl \
+    -p index_wallet_activity="wallets=$USER_ADDRESS" \
+    -p map_pure_dune_pnl="wallets=$USER_ADDRESS" \
     --start-block $START_BLOCK \
     --stop-block $END_BLOCK \
     --substreams-endpoint polygon.streamingfast.io:443 | \